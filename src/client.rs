@@ -0,0 +1,198 @@
+//! ## Client
+//!
+//! HTTP client used to send WebDAV requests
+
+use bytes::Bytes;
+use http::Method;
+use reqwest::blocking::Response;
+use reqwest::redirect::Policy;
+use reqwest::Url;
+
+use crate::webdav_xml::elements::{Depth, Timeout};
+
+/// WebDAV http client.
+///
+/// Redirects are never followed by the client itself, since they are handled by [`crate::RedirectPolicy`].
+pub struct Client {
+    username: String,
+    password: String,
+    client: reqwest::blocking::Client,
+}
+
+impl Client {
+    /// Initialize a new client with the provided credentials, failing if the http client cannot be built
+    pub fn init(username: &str, password: &str) -> reqwest::Result<Self> {
+        Ok(Client {
+            username: username.to_string(),
+            password: password.to_string(),
+            client: reqwest::blocking::Client::builder()
+                .redirect(Policy::none())
+                .build()?,
+        })
+    }
+
+    /// Name of the user the client authenticates as
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Send `request`; basic authentication is used only if `credentials` is `true`.
+    pub fn execute(&self, request: &Request, credentials: bool) -> reqwest::Result<Response> {
+        let method = reqwest::Method::from_bytes(request.method.as_str().as_bytes())
+            .expect("method is valid");
+        let mut builder = self.client.request(method, request.url.clone());
+        if credentials {
+            builder = builder.basic_auth(&self.username, Some(&self.password));
+        }
+        for (name, value) in &request.headers {
+            builder = builder.header(*name, value);
+        }
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+
+        builder.send()
+    }
+}
+
+/// Get the WebDAV extension method called `name`, such as `PROPFIND`
+pub(crate) fn method(name: &'static str) -> Method {
+    Method::from_bytes(name.as_bytes()).expect("WebDAV method names are valid tokens")
+}
+
+/// A WebDAV request which can be sent multiple times
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: Method,
+    pub url: Url,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Option<Bytes>,
+}
+
+impl Request {
+    pub fn new(method: Method, url: Url) -> Self {
+        Self {
+            method,
+            url,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// Get the capabilities of the server for the resource at `url`
+    #[cfg(feature = "find")]
+    pub fn options(url: Url) -> Self {
+        Self::new(Method::OPTIONS, url)
+    }
+
+    /// Get a file
+    pub fn get(url: Url) -> Self {
+        Self::new(Method::GET, url)
+    }
+
+    /// Upload a file
+    pub fn put(url: Url, body: Bytes) -> Self {
+        Self::new(Method::PUT, url)
+            .header("content-type", "application/octet-stream")
+            .body(body)
+    }
+
+    /// Delete a collection or a file
+    pub fn delete(url: Url) -> Self {
+        Self::new(Method::DELETE, url)
+    }
+
+    /// Create a collection
+    pub fn mkcol(url: Url) -> Self {
+        Self::new(method("MKCOL"), url)
+    }
+
+    /// Move a collection or a file to `dest`
+    pub fn mv(url: Url, dest: &Url) -> Self {
+        Self::new(method("MOVE"), url).header("destination", dest.as_str())
+    }
+
+    /// Copy a collection or a file to `dest`, overwriting it
+    pub fn copy(url: Url, dest: &Url) -> Self {
+        Self::new(method("COPY"), url)
+            .header("destination", dest.as_str())
+            .header("overwrite", "T")
+    }
+
+    /// Find the properties of the resource at `url` and, depending on `depth`, of its members, `body` being
+    /// a `propfind` element
+    pub fn propfind(url: Url, depth: Depth, body: Bytes) -> Self {
+        Self::new(method("PROPFIND"), url)
+            .header("depth", depth)
+            .header("content-type", "application/xml; charset=utf-8")
+            .body(body)
+    }
+
+    /// Set and remove properties of the resource at `url`, `body` being a `propertyupdate` element
+    pub fn proppatch(url: Url, body: Bytes) -> Self {
+        Self::new(method("PROPPATCH"), url)
+            .header("content-type", "application/xml; charset=utf-8")
+            .body(body)
+    }
+
+    /// Replace the access control list of the resource at `url`, `body` being an `acl` element
+    pub fn acl(url: Url, body: Bytes) -> Self {
+        Self::new(method("ACL"), url)
+            .header("content-type", "application/xml; charset=utf-8")
+            .body(body)
+    }
+
+    /// Search the resources matching a query, `body` being a `searchrequest` element
+    pub fn search(url: Url, body: Bytes) -> Self {
+        Self::new(method("SEARCH"), url)
+            .header("content-type", "application/xml; charset=utf-8")
+            .body(body)
+    }
+
+    /// Run the report in `body` on the resource at `url`, e.g. a `sync-collection` report
+    pub fn report(url: Url, depth: Depth, body: Bytes) -> Self {
+        Self::new(method("REPORT"), url)
+            .header("depth", depth)
+            .header("content-type", "application/xml; charset=utf-8")
+            .body(body)
+    }
+
+    /// Lock the resource at `url`, `body` being a `lockinfo` element
+    pub fn lock(url: Url, depth: Depth, timeout: Timeout, body: Bytes) -> Self {
+        Self::new(method("LOCK"), url)
+            .header("depth", depth)
+            .header("timeout", timeout)
+            .header("content-type", "application/xml; charset=utf-8")
+            .body(body)
+    }
+
+    /// Refresh the lock identified by `token` on the resource at `url`
+    pub fn refresh_lock(url: Url, token: &str, timeout: Timeout) -> Self {
+        Self::new(method("LOCK"), url)
+            .header("if", format!("(<{token}>)"))
+            .header("timeout", timeout)
+    }
+
+    /// Release the lock identified by `token` on the resource at `url`
+    pub fn unlock(url: Url, token: &str) -> Self {
+        Self::new(method("UNLOCK"), url).header("lock-token", format!("<{token}>"))
+    }
+
+    pub fn header(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+
+    /// Get the value of the header `name`, if set
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn body(mut self, body: Bytes) -> Self {
+        self.body = Some(body);
+        self
+    }
+}
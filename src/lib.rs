@@ -31,47 +31,895 @@
 #[macro_use]
 extern crate log;
 
+mod client;
+mod lock;
 #[cfg(test)]
 mod mock;
 mod parser;
-mod webdav_xml;
+mod principal;
+mod quota;
+mod redirect;
+mod retry;
+mod search;
+mod sync;
+mod tree;
+mod version;
+pub mod webdav_xml;
 
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use remotefs::fs::{Metadata, ReadStream, UnixPex, Welcome, WriteStream};
 use remotefs::{File, RemoteError, RemoteErrorType, RemoteFs, RemoteResult};
-use rustydav::client::Client;
-
-use self::parser::ResponseParser;
+use reqwest::Url;
+#[cfg(feature = "find")]
+use wildmatch::WildMatch;
+
+use self::client::{Client, Request};
+pub use self::lock::Lock;
+pub use self::parser::{Entries, Entry, EntryError, Listing, PropertyResult};
+use self::parser::{PrincipalIds, ResponseParser};
+pub use self::principal::CurrentPrincipal;
+pub use self::quota::{InsufficientStorage, Quota};
+pub use self::redirect::RedirectPolicy;
+pub use self::retry::RetryPolicy;
+pub use self::search::{Comparison, Filter, SearchProperty, SearchQuery};
+pub use self::sync::{SyncChanges, SyncResult};
+pub use self::tree::Tree;
+pub use self::version::Version;
+pub use self::webdav_xml::elements::{
+    Access, Ace, ActiveLock, Depth, Href, LockEntry, LockRoot, LockScope, LockToken, LockType,
+    Principal, Privilege, SyncLevel, Timeout,
+};
+use self::webdav_xml::elements::{
+    Limit, LockInfo, Multistatus, Properties, PropertyUpdate, Propfind, Remove, SearchRequest, Set,
+    SyncCollection, SyncToken,
+};
+use self::webdav_xml::properties::{
+    Acl, AddressbookHomeSet, CalendarHomeSet, ContentLength, CreationDate, CurrentUserPrincipal,
+    CurrentUserPrivilegeSet, DisplayName, ETag, Group, LastModified, PrincipalUrl,
+    QuotaAvailableBytes, QuotaUsedBytes, ResourceType,
+};
+pub use self::webdav_xml::properties::{Owner, QuotaBytes};
+use self::webdav_xml::IntoXml;
 
 /// WebDAV remote fs client
 pub struct WebDAVFs {
-    client: Client,
+    client: Result<Client, String>,
     url: String,
     wrkdir: String,
     connected: bool,
+    retry: RetryPolicy,
+    redirect: RedirectPolicy,
+    moved: Vec<(Url, Url)>,
+    strict_listing: bool,
+    principal_ids: Option<PrincipalIds>,
+    locks: Vec<Lock>,
+    lock_protected: bool,
+    listing_properties: Properties,
+    current_user_privileges: bool,
+    insufficient_storage: Option<InsufficientStorage>,
+    dasl: Option<bool>,
+    sync_level: SyncLevel,
+    sync_limit: Option<u64>,
 }
 
+/// Timeout of the locks taken in lock protected mode, in seconds
+const PROTECTED_LOCK_TIMEOUT: u64 = 300;
+/// Maximum number of directories listed in parallel when walking a tree
+const TREE_WALK_WORKERS: usize = 4;
+
+/// Permanent redirects followed by a request, from the redirected url to its target
+type Redirects = Vec<(Url, Url)>;
+
 impl WebDAVFs {
     /// Create a new WebDAVFs instance
     pub fn new(username: &str, password: &str, url: &str) -> WebDAVFs {
         WebDAVFs {
-            client: Client::init(username, password),
+            client: Client::init(username, password).map_err(|err| err.to_string()),
             url: url.to_string(),
             wrkdir: String::from("/"),
             connected: false,
+            retry: RetryPolicy::none(),
+            redirect: RedirectPolicy::default(),
+            moved: Vec::new(),
+            strict_listing: false,
+            principal_ids: None,
+            locks: Vec::new(),
+            lock_protected: false,
+            listing_properties: Properties::new(),
+            current_user_privileges: false,
+            insufficient_storage: None,
+            dasl: None,
+            sync_level: SyncLevel::One,
+            sync_limit: None,
+        }
+    }
+
+    /// Set the policy used to retry requests failing with a transient error.
+    ///
+    /// By default requests are never retried.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Set the policy used to follow redirects.
+    ///
+    /// By default redirects are followed for `GET`, `HEAD`, `OPTIONS`, `PROPFIND` and `PUT` requests.
+    pub fn redirect_policy(mut self, policy: RedirectPolicy) -> Self {
+        self.redirect = policy;
+        self
+    }
+
+    /// Set whether listings must fail when the server reports an error status for any of the children of the
+    /// listed directory, e.g. an unreadable child.
+    ///
+    /// By default these entries are omitted from [`RemoteFs::list_dir`]; use [`WebDAVFs::list_dir_with_errors`]
+    /// to get them.
+    pub fn strict_listing(mut self, strict: bool) -> Self {
+        self.strict_listing = strict;
+        self
+    }
+
+    /// Set whether `create_file`, `mov` and `remove_file` must run holding an exclusive write lock on the
+    /// target resource (both the source and the destination for `mov`), as desktop WebDAV clients do.
+    ///
+    /// The lock is taken before the operation and released after it, whether it succeeded or not; if the
+    /// resource is already locked by someone else the operation fails with [`RemoteErrorType::PexError`]
+    /// without being attempted. Resources already covered by a lock taken with [`WebDAVFs::lock`] are not
+    /// locked again. Locking a file which doesn't exist yet creates it empty; the empty file is removed if the
+    /// operation fails.
+    ///
+    /// `setstat` is not supported by this client, so it is never locked. Disabled by default.
+    pub fn lock_protected(mut self, protected: bool) -> Self {
+        self.lock_protected = protected;
+        self
+    }
+
+    /// Set additional properties to request when listing directories and getting the metadata of files.
+    ///
+    /// Listings request only `resourcetype`, `getcontentlength`, `getlastmodified`, `creationdate` and
+    /// `getetag` by default. The properties reported on each [`Entry`] must be requested here, e.g.
+    /// [`Executable`](webdav_xml::properties::Executable) to get the file mode or
+    /// [`LockDiscovery`](webdav_xml::properties::LockDiscovery) to get the locks held on it.
+    pub fn listing_properties(mut self, properties: Properties) -> Self {
+        self.listing_properties = properties;
+        self
+    }
+
+    /// Set whether listings request the privileges of the current user on each entry (RFC 3744
+    /// `current-user-privilege-set`).
+    ///
+    /// The privileges are reported on each [`Entry`] and mapped to the user bits of the file mode: `read` to
+    /// read (and execute for directories), `write` or `write-content` to write files and `write` or both
+    /// `bind` and `unbind` to write directories; `all` grants everything. Servers without access control
+    /// support don't report the property, so the mode is left as it is. Disabled by default.
+    pub fn current_user_privileges(mut self, request: bool) -> Self {
+        self.current_user_privileges = request;
+        self
+    }
+
+    /// Set the function used to map the owner and group principals reported by the server (e.g. `alice` or
+    /// `/principals/users/alice`) to the `uid` and `gid` of the file metadata.
+    ///
+    /// By default no mapping is applied; the principals are still available on each [`Entry`] when the `owner`
+    /// and `group` properties are requested with [`WebDAVFs::listing_properties`]. They are always requested
+    /// when a mapping is set.
+    pub fn principal_ids<F>(mut self, map: F) -> Self
+    where
+        F: Fn(&str) -> Option<u32> + Send + Sync + 'static,
+    {
+        self.principal_ids = Some(Arc::new(map));
+        self
+    }
+
+    /// Set whether [`WebDAVFs::sync_collection`] reports the changes of the members of the collection only
+    /// ([`SyncLevel::One`], the default) or of the whole tree ([`SyncLevel::Infinite`]).
+    ///
+    /// Servers are only required to support [`SyncLevel::One`].
+    pub fn sync_level(mut self, level: SyncLevel) -> Self {
+        self.sync_level = level;
+        self
+    }
+
+    /// Set the maximum number of changes returned by each [`WebDAVFs::sync_collection`]; by default the server
+    /// returns all of them.
+    pub fn sync_limit(mut self, limit: u64) -> Self {
+        self.sync_limit = Some(limit);
+        self
+    }
+
+    /// List the directory at `path`, returning both its children and the children which the server reported
+    /// with an error status.
+    pub fn list_dir_with_errors(&mut self, path: &Path) -> RemoteResult<Listing> {
+        let listing = self.open(path, true)?.1.into_listing()?;
+        for entry in &listing.errors {
+            warn!("could not list {}: {}", entry.path.display(), entry.error);
+        }
+
+        Ok(listing)
+    }
+
+    /// Get the quota of the collection at `path`, e.g. to check the available space before a big upload.
+    ///
+    /// Values which the server doesn't report are [`QuotaBytes::Unknown`].
+    pub fn quota(&mut self, path: &Path) -> RemoteResult<Quota> {
+        let url = self.url(path, false)?;
+        debug!("Getting quota of {}", url);
+        let prop = Properties::new()
+            .with::<QuotaAvailableBytes>()
+            .with::<QuotaUsedBytes>();
+        let body = Propfind::Prop(prop)
+            .into_xml()
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        self.send(Request::propfind(url, Depth::Zero, body))?
+            .quota()
+    }
+
+    /// The write refused by the server for insufficient storage by the last call to `create_file`, along with the
+    /// quota of its collection.
+    ///
+    /// When `create_file` fails with `507 Insufficient Storage`, the quota is read right away; the returned
+    /// error reports the same snapshot. Each call to `create_file` resets it, so it is `Some` right after a
+    /// failed write if and only if the server reported insufficient storage, telling that failure apart from
+    /// other I/O errors.
+    pub fn last_insufficient_storage(&self) -> Option<&InsufficientStorage> {
+        self.insufficient_storage.as_ref()
+    }
+
+    /// List the directory at `path` lazily, yielding its children as soon as they are received from the server,
+    /// e.g. to display or filter the entries of a huge directory while it is still being listed.
+    ///
+    /// Children which the server reported with an error status are collected in [`Entries::errors`], unless
+    /// [`WebDAVFs::strict_listing`] is set, in which case the first of them ends the iteration with its error.
+    /// The iterator can be dropped at any time, which closes the connection.
+    pub fn list_dir_iter(&mut self, path: &Path) -> RemoteResult<Entries> {
+        let strict = self.strict_listing;
+        self.open(path, true)
+            .map(|(_, entries)| entries.strict(strict))
+    }
+
+    /// List the whole tree under the directory at `path` with a single `Depth: infinity` listing.
+    ///
+    /// Servers which refuse infinite depth listings (`403` with the `propfind-finite-depth` precondition) are
+    /// walked instead with a `Depth: 1` listing of each directory, listing the directories of the same level
+    /// in parallel.
+    ///
+    /// Entries which the server reported with an error status, e.g. unreadable directories, are reported in
+    /// [`Tree::errors`], unless [`WebDAVFs::strict_listing`] is set, in which case the listing fails.
+    pub fn list_tree(&mut self, path: &Path) -> RemoteResult<Tree> {
+        let url = self.url(path, true)?;
+        debug!("Listing tree: {}", url);
+        let listing = match self.list_depth(url.clone(), Depth::Infinity) {
+            Ok((_, listing, redirects)) => {
+                for (from, to) in redirects {
+                    self.rebase(&from, &to);
+                }
+                listing
+            }
+            Err(err) if err.kind == RemoteErrorType::UnsupportedFeature => {
+                debug!("infinite depth listing refused ({err}); walking the tree");
+                self.walk_tree(url)?
+            }
+            Err(err) => return Err(err),
+        };
+        if let (true, Some(entry)) = (self.strict_listing, listing.errors.first()) {
+            return Err(entry.error.clone());
+        }
+        for entry in &listing.errors {
+            warn!("could not list {}: {}", entry.path.display(), entry.error);
+        }
+
+        Tree::from_listing(listing)
+            .ok_or_else(|| RemoteError::new(RemoteErrorType::NoSuchFileOrDirectory))
+    }
+
+    /// Search the resources matching `query` with a DASL `SEARCH` request
+    /// ([RFC 5323](https://www.rfc-editor.org/rfc/rfc5323)), sent to the searched collection:
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    ///
+    /// use remotefs_webdav::{Filter, SearchProperty, SearchQuery, WebDAVFs};
+    ///
+    /// let mut client = WebDAVFs::new("alice", "secret", "http://localhost:3080");
+    /// let query = SearchQuery::new(Path::new("/docs/"))
+    ///     .filter(Filter::size_gt(1024 * 1024))
+    ///     .order_by(SearchProperty::Size, true)
+    ///     .limit(10);
+    /// let listing = client.search(query).unwrap();
+    /// ```
+    ///
+    /// The results have the properties requested by listings and are parsed into the same entries as
+    /// [`WebDAVFs::list_dir_with_errors`]; results which the server reported with an error status are returned
+    /// in [`Listing::errors`].
+    pub fn search(&mut self, query: SearchQuery) -> RemoteResult<Listing> {
+        let url = self.url(query.scope(), true)?;
+        debug!("Searching {}", url);
+        let href = url
+            .as_str()
+            .parse()
+            .map(Href)
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::BadAddress, e))?;
+        let body = SearchRequest(query.basicsearch(href, self.listing_prop()))
+            .into_xml()
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        let listing = self
+            .send(Request::search(url, body))?
+            .entries()?
+            .principal_ids(self.principal_ids.clone())
+            .into_listing()?;
+        for entry in &listing.errors {
+            warn!("could not search {}: {}", entry.path.display(), entry.error);
+        }
+
+        Ok(listing)
+    }
+
+    /// Get the changes of the collection at `path` since `token` with the `sync-collection` report
+    /// ([RFC 6578](https://www.rfc-editor.org/rfc/rfc6578)), e.g. to keep a local copy up to date without listing
+    /// the whole collection:
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    ///
+    /// use remotefs_webdav::{SyncResult, WebDAVFs};
+    ///
+    /// let mut client = WebDAVFs::new("alice", "secret", "http://localhost:3080");
+    /// let mut token = None;
+    /// loop {
+    ///     match client.sync_collection(Path::new("/docs/"), token.as_deref()).unwrap() {
+    ///         SyncResult::Changes(changes) => {
+    ///             // apply changes.entries and changes.removed
+    ///             token = Some(changes.token);
+    ///         }
+    ///         // list the collection again, then start over
+    ///         SyncResult::ResyncRequired => token = None,
+    ///     }
+    ///     # break;
+    /// }
+    /// ```
+    ///
+    /// Without `token`, all the members of the collection are reported along with the initial token. Changed
+    /// members are parsed into the same entries as listings; removed ones are only reported by path.
+    ///
+    /// Members which the server reported with another error status are returned in [`SyncChanges::errors`],
+    /// unless [`WebDAVFs::strict_listing`] is set, in which case the synchronization fails.
+    pub fn sync_collection(
+        &mut self,
+        path: &Path,
+        token: Option<&str>,
+    ) -> RemoteResult<SyncResult> {
+        let url = self.url(path, true)?;
+        debug!("Synchronizing {} from token {:?}", url, token);
+        let body = SyncCollection {
+            sync_token: SyncToken(token.unwrap_or_default().into()),
+            sync_level: self.sync_level,
+            limit: self.sync_limit.map(Limit),
+            prop: self.listing_prop(),
+        }
+        .into_xml()
+        .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        let result = self
+            .send(Request::report(url, Depth::Zero, body))?
+            .sync(self.principal_ids.clone())?;
+        if let SyncResult::Changes(changes) = &result {
+            if let (true, Some(entry)) = (self.strict_listing, changes.errors.first()) {
+                return Err(entry.error.clone());
+            }
+            for entry in &changes.errors {
+                warn!(
+                    "could not synchronize {}: {}",
+                    entry.path.display(),
+                    entry.error
+                );
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Find the properties of the resource at `path` and, depending on `depth`, of its members.
+    ///
+    /// The properties to find are selected by `propfind`, e.g. server specific properties:
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    ///
+    /// use remotefs_webdav::webdav_xml::elements::{Properties, Propfind};
+    /// use remotefs_webdav::{Depth, WebDAVFs};
+    ///
+    /// let mut client = WebDAVFs::new("alice", "secret", "http://localhost:3080");
+    /// let prop = Properties::new().with_name("http://owncloud.org/ns", "oc", "fileid");
+    /// let multistatus = client
+    ///     .propfind(Path::new("/docs/"), Depth::One, Propfind::Prop(prop))
+    ///     .unwrap();
+    /// ```
+    pub fn propfind(
+        &mut self,
+        path: &Path,
+        depth: Depth,
+        propfind: Propfind,
+    ) -> RemoteResult<Multistatus> {
+        let url = self.url(path, false)?;
+        debug!("Finding properties of {} (depth {})", url, depth);
+        let body = propfind
+            .into_xml()
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        self.send(Request::propfind(url, depth, body))?
+            .multistatus()
+    }
+
+    /// Set the properties in `set` along with their values and remove the properties in `remove` from the
+    /// resource at `path`, e.g. dead properties of a custom namespace:
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    ///
+    /// use remotefs_webdav::webdav_xml::elements::Properties;
+    /// use remotefs_webdav::WebDAVFs;
+    ///
+    /// let mut client = WebDAVFs::new("alice", "secret", "http://localhost:3080");
+    /// let set = Properties::new().with_name_value("http://example.com/ns", "ex", "color", "red");
+    /// let remove = Properties::new().with_name("http://example.com/ns", "ex", "shape");
+    /// let results = client
+    ///     .proppatch(Path::new("/docs/report.odt"), set, remove)
+    ///     .unwrap();
+    /// ```
+    ///
+    /// Returns the outcome of the update of each property. Since the server applies either all the
+    /// instructions or none of them, the properties which could be updated are usually reported with a
+    /// `424 Failed Dependency` error when another one failed.
+    pub fn proppatch(
+        &mut self,
+        path: &Path,
+        set: Properties,
+        remove: Properties,
+    ) -> RemoteResult<Vec<PropertyResult>> {
+        if set.is_empty() && remove.is_empty() {
+            return Ok(Vec::new());
+        }
+        let url = self.url(path, false)?;
+        debug!("Updating properties of {}", url);
+        let requested = set
+            .names()
+            .chain(remove.names())
+            .map(|(namespace, name)| (namespace.map(str::to_string), name.to_string()))
+            .collect();
+        let body = PropertyUpdate {
+            set: (!set.is_empty()).then_some(Set(set)),
+            remove: (!remove.is_empty()).then_some(Remove(remove)),
+        }
+        .into_xml()
+        .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        self.send(Request::proppatch(url, body))?
+            .property_results(requested)
+    }
+
+    /// Get the principal the client is authenticated as, with the `current-user-principal` property (RFC 5397)
+    /// of the server root.
+    ///
+    /// The principal resource is then read for its `displayname` and `principal-URL` and for the CalDAV
+    /// `calendar-home-set` and CardDAV `addressbook-home-set`, which are only reported by calendar and contacts
+    /// servers. Returns `None` if the server doesn't consider the client authenticated; fails with
+    /// [`RemoteErrorType::UnsupportedFeature`] if the server doesn't report the current principal.
+    pub fn current_principal(&mut self) -> RemoteResult<Option<CurrentPrincipal>> {
+        let url = self.url(Path::new("/"), true)?;
+        debug!("Getting current principal from {}", url);
+        let body = Propfind::Prop(Properties::new().with::<CurrentUserPrincipal>())
+            .into_xml()
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        let Some(url) = self
+            .send(Request::propfind(url, Depth::Zero, body))?
+            .current_user_principal()?
+        else {
+            debug!("client is not authenticated");
+            return Ok(None);
+        };
+
+        debug!("Reading principal {}", url);
+        let body = Propfind::Prop(
+            Properties::new()
+                .with::<DisplayName>()
+                .with::<PrincipalUrl>()
+                .with::<CalendarHomeSet>()
+                .with::<AddressbookHomeSet>(),
+        )
+        .into_xml()
+        .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        self.send(Request::propfind(url.clone(), Depth::Zero, body))?
+            .principal(url)
+            .map(Some)
+    }
+
+    /// Get the access control list of the resource at `path` (RFC 3744 `acl` property).
+    ///
+    /// The entries are returned in evaluation order, including the protected and inherited ones. Fails with
+    /// [`RemoteErrorType::UnsupportedFeature`] if the server doesn't report the property.
+    pub fn get_acl(&mut self, path: &Path) -> RemoteResult<Vec<Ace>> {
+        let url = self.url(path, false)?;
+        debug!("Getting ACL of {}", url);
+        let body = Propfind::Prop(Properties::new().with::<Acl>())
+            .into_xml()
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        self.send(Request::propfind(url, Depth::Zero, body))?.acl()
+    }
+
+    /// Replace the access control list of the resource at `path` with `aces`, sending the `ACL` method.
+    ///
+    /// Protected and inherited entries can't be changed, so they are left out of the request. Violated
+    /// preconditions are mapped to errors, e.g. [`RemoteErrorType::PexError`] for conflicting entries and
+    /// [`RemoteErrorType::UnsupportedFeature`] for privileges or entries the server doesn't support.
+    pub fn set_acl(&mut self, path: &Path, aces: Vec<Ace>) -> RemoteResult<()> {
+        let url = self.url(path, false)?;
+        debug!("Setting ACL of {}", url);
+        let aces = aces
+            .into_iter()
+            .filter(|ace| !ace.protected && ace.inherited.is_none())
+            .collect();
+        let body = Acl(aces)
+            .into_xml()
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        self.send(Request::acl(url, body))?.status()
+    }
+
+    /// Take a write lock on the resource at `path`.
+    ///
+    /// `depth` must be either [`Depth::Zero`] or [`Depth::Infinity`]; the server may grant a timeout other
+    /// than the requested one, which is reported by [`Lock::timeout`]. `owner` is stored by the server along
+    /// with the lock, e.g. to tell other clients who is holding it.
+    ///
+    /// Until the lock is released with [`WebDAVFs::unlock`], its token is submitted with every request
+    /// modifying a resource covered by the lock.
+    pub fn lock(
+        &mut self,
+        path: &Path,
+        scope: LockScope,
+        depth: Depth,
+        timeout: Timeout,
+        owner: Option<&str>,
+    ) -> RemoteResult<Lock> {
+        if depth == Depth::One {
+            return Err(RemoteError::new_ex(
+                RemoteErrorType::UnsupportedFeature,
+                "locks with depth 1 are not allowed",
+            ));
+        }
+        let path = self.path(path);
+        let url = self.url(&path, false)?;
+        debug!(
+            "Locking {} ({:?}, depth {}, {})",
+            url, scope, depth, timeout
+        );
+        let body = LockInfo {
+            lockscope: scope,
+            locktype: LockType::Write,
+            owner: owner.map(|owner| Owner(owner.into())),
+        }
+        .into_xml()
+        .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+
+        let mut response = self.send(Request::lock(url, depth, timeout, body))?;
+        response.status()?;
+        let created = response.status_code() == 201;
+        let url = response.url().clone();
+        let header_token = response
+            .header("lock-token")
+            .and_then(lock::parse_lock_token);
+        let locks = response.locks()?;
+        // the server reports all the locks of the resource; find the new one by its token
+        let active = match &header_token {
+            Some(token) => locks
+                .into_iter()
+                .find(|lock| lock.locktoken.as_ref().is_some_and(|t| &*t.0 == token)),
+            None => locks.into_iter().find(|lock| lock.locktoken.is_some()),
+        };
+        let token = header_token
+            .or_else(|| Some(active.as_ref()?.locktoken.as_ref()?.0.to_string()))
+            .ok_or_else(|| {
+                RemoteError::new_ex(
+                    RemoteErrorType::ProtocolError,
+                    format!("LOCK {url}: missing lock token in response"),
+                )
+            })?;
+        let lock = Lock::new(
+            path,
+            url,
+            token,
+            scope,
+            depth,
+            Some(active.and_then(|lock| lock.timeout).unwrap_or(timeout)),
+            created,
+        );
+        debug!("Acquired lock {}", lock.token());
+        self.locks.push(lock.clone());
+
+        Ok(lock)
+    }
+
+    /// Refresh `lock`, requesting a new `timeout`; the timeout granted by the server is stored in `lock`.
+    pub fn refresh_lock(&mut self, lock: &mut Lock, timeout: Timeout) -> RemoteResult<()> {
+        debug!("Refreshing lock {} on {}", lock.token(), lock.url());
+        let response = self.send(Request::refresh_lock(
+            lock.url().clone(),
+            lock.token(),
+            timeout,
+        ))?;
+        let active = response.locks()?.into_iter().find(|active| {
+            active
+                .locktoken
+                .as_ref()
+                .is_some_and(|t| &*t.0 == lock.token())
+        });
+        lock.set_timeout(Some(
+            active.and_then(|lock| lock.timeout).unwrap_or(timeout),
+        ));
+        for held in self.locks.iter_mut() {
+            if held.token() == lock.token() {
+                held.set_timeout(lock.timeout());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Release `lock`.
+    ///
+    /// The lock token is no longer submitted with the following requests, even if the server fails to
+    /// release the lock (e.g. because it has already expired).
+    pub fn unlock(&mut self, lock: Lock) -> RemoteResult<()> {
+        debug!("Releasing lock {} on {}", lock.token(), lock.url());
+        self.locks.retain(|held| held.token() != lock.token());
+        self.send(Request::unlock(lock.url().clone(), lock.token()))?
+            .status()
+    }
+
+    /// Locks taken with [`WebDAVFs::lock`] which have not been released yet
+    pub fn locks(&self) -> &[Lock] {
+        &self.locks
+    }
+
+    /// Send `request`, retrying it according to the retry policy and following redirects according to the
+    /// redirect policy.
+    ///
+    /// Permanent redirects are remembered for the following requests.
+    fn send(&mut self, request: Request) -> RemoteResult<ResponseParser> {
+        let (response, redirects) = self.dispatch(request)?;
+        for (from, to) in redirects {
+            self.rebase(&from, &to);
+        }
+
+        Ok(response)
+    }
+
+    /// Get the http client, failing if it could not be built
+    fn client(&self) -> RemoteResult<&Client> {
+        self.client.as_ref().map_err(|err| {
+            RemoteError::new_ex(
+                RemoteErrorType::ConnectionError,
+                format!("failed to build http client: {err}"),
+            )
+        })
+    }
+
+    /// Send `request` as [`WebDAVFs::send`] does, returning the permanent redirects which have been followed
+    /// instead of remembering them.
+    fn dispatch(&self, request: Request) -> RemoteResult<(ResponseParser, Redirects)> {
+        let origin = request.url.origin();
+        let mut request = self.submit_lock_tokens(request);
+        let mut credentials = true;
+        let mut hops = 0;
+        let mut redirects = Vec::new();
+        let client = self.client()?;
+        loop {
+            let response = self
+                .retry
+                .run(&request.method, || client.execute(&request, credentials))
+                .map_err(|e| {
+                    let kind = if e.is_connect() || e.is_timeout() {
+                        RemoteErrorType::ConnectionError
+                    } else {
+                        RemoteErrorType::ProtocolError
+                    };
+                    RemoteError::new_ex(kind, format!("{} {}: {e}", request.method, request.url))
+                })?;
+            let Some(next) = self.redirect.next(&request, &response) else {
+                return Ok((ResponseParser::new(request.method, response), redirects));
+            };
+            if hops >= self.redirect.hops() {
+                return Err(RemoteError::new_ex(
+                    RemoteErrorType::ProtocolError,
+                    format!("too many redirects for {} {}", request.method, request.url),
+                ));
+            }
+            hops += 1;
+            debug!(
+                "{} {} redirected ({}) to {}",
+                request.method,
+                request.url,
+                response.status(),
+                next.url
+            );
+            if next.url.origin() != origin {
+                debug!("dropping credentials for cross-origin redirect");
+                credentials = false;
+            } else if RedirectPolicy::is_permanent(response.status().as_u16()) {
+                redirects.push((request.url.clone(), next.url.clone()));
+            }
+            request = next;
+        }
+    }
+
+    /// Download the resource at `url`, writing its content to `dest`
+    fn download(
+        &mut self,
+        url: Url,
+        mut dest: Box<dyn std::io::Write + Send>,
+    ) -> RemoteResult<u64> {
+        let mut response_parser = self.send(Request::get(url))?;
+        response_parser.status()?;
+
+        // write to dest
+        let mut buf = vec![0; 1024];
+        let mut total_size = 0;
+        loop {
+            let n = response_parser
+                .read(&mut buf)
+                .map_err(|e| RemoteError::new_ex(RemoteErrorType::IoError, e))?;
+            total_size += n as u64;
+            if n == 0 {
+                return Ok(total_size);
+            }
+            dest.write_all(&buf[..n])
+                .map_err(|e| RemoteError::new_ex(RemoteErrorType::IoError, e))?;
+        }
+    }
+
+    /// Run `op` holding an exclusive write lock on `path` if lock protected mode is enabled.
+    ///
+    /// If `path` was unmapped, locking it creates an empty resource, which is removed if `op` fails.
+    fn protected<T>(
+        &mut self,
+        path: &Path,
+        op: impl FnOnce(&mut Self) -> RemoteResult<T>,
+    ) -> RemoteResult<T> {
+        if !self.lock_protected {
+            return op(self);
+        }
+        let url = self.url(path, false)?;
+        if self.locks.iter().any(|lock| lock.covers(&url)) {
+            debug!("{} is already locked by this client", url);
+            return op(self);
+        }
+        let owner = self.client()?.username().to_string();
+        let lock = self.lock(
+            path,
+            LockScope::Exclusive,
+            Depth::Zero,
+            Timeout::Seconds(PROTECTED_LOCK_TIMEOUT),
+            Some(&owner),
+        )?;
+        let result = op(self);
+        if result.is_err() && lock.created() {
+            debug!("removing {} created by locking it", url);
+            if let Err(err) = self.send(Request::delete(url)).and_then(|mut r| r.status()) {
+                debug!("could not remove {}: {err}", path.display());
+            }
+        }
+        // the lock is already gone if the resource has been moved or removed
+        if let Err(err) = self.unlock(lock) {
+            debug!("could not release lock on {}: {err}", path.display());
+        }
+
+        result
+    }
+
+    /// Build the error of a write of `size` bytes to `path` refused for insufficient storage, reading the quota
+    /// of the parent collection
+    fn insufficient_storage(&mut self, path: &Path, size: u64) -> InsufficientStorage {
+        let path = self.path(path);
+        let parent = path.parent().unwrap_or(Path::new("/")).to_path_buf();
+        let quota = match self.quota(&parent) {
+            Ok(quota) => Some(quota),
+            Err(err) => {
+                debug!("could not read quota of {}: {err}", parent.display());
+                None
+            }
+        };
+
+        InsufficientStorage { path, size, quota }
+    }
+
+    /// Get the properties requested in listings
+    fn listing_prop(&self) -> Properties {
+        let mut prop = Properties::new()
+            .with::<ResourceType>()
+            .with::<ContentLength>()
+            .with::<LastModified>()
+            .with::<CreationDate>()
+            .with::<ETag>();
+        if self.principal_ids.is_some() {
+            prop = prop.with::<Owner>().with::<Group>();
+        }
+        if self.current_user_privileges {
+            prop = prop.with::<CurrentUserPrivilegeSet>();
+        }
+
+        prop.with_properties(self.listing_properties.clone())
+    }
+
+    /// Add the `If` header with the tokens of the held locks covering the resources modified by `request`
+    fn submit_lock_tokens(&self, request: Request) -> Request {
+        if self.locks.is_empty()
+            || !lock::is_mutating(&request.method)
+            || request.header_value("if").is_some()
+        {
+            return request;
+        }
+        let destination = request
+            .header_value("destination")
+            .and_then(|dest| Url::parse(dest).ok());
+        let tokens = lock::if_header(
+            &request.url,
+            self.locks.iter().filter(|lock| {
+                lock.covers(&request.url)
+                    || destination.as_ref().is_some_and(|dest| lock.covers(dest))
+            }),
+        );
+        match tokens {
+            Some(tokens) => request.header("if", tokens),
+            None => request,
+        }
+    }
+
+    /// Remember the permanent redirect from `from` to `to`.
+    ///
+    /// The server base url is updated only if `from` is the base collection itself; other resources are
+    /// remembered one by one, along with the members of moved collections.
+    fn rebase(&mut self, from: &Url, to: &Url) {
+        if from.as_str().trim_end_matches('/') == self.url.trim_end_matches('/') {
+            let new_base = to.as_str().trim_end_matches('/');
+            debug!("server base url moved permanently to {new_base}");
+            self.url = new_base.to_string();
+            return;
+        }
+        debug!("{from} moved permanently to {to}");
+        self.moved.retain(|(moved, _)| moved != from);
+        self.moved.push((from.clone(), to.clone()));
+    }
+
+    /// Get the url `url` has been permanently moved to, if any
+    fn follow_moved(&self, url: Url) -> Url {
+        for (from, to) in self.moved.iter().rev() {
+            if url.as_str().trim_end_matches('/') == from.as_str().trim_end_matches('/') {
+                return to.clone();
+            }
+            // a member of a moved collection; the prefix ends with a slash, so it stops at a segment boundary
+            if !from.path().ends_with('/') || !to.path().ends_with('/') {
+                continue;
+            }
+            if let Some(member) = url.as_str().strip_prefix(from.as_str()) {
+                if let Ok(moved) = Url::parse(&format!("{to}{member}")) {
+                    return moved;
+                }
+            }
         }
+
+        url
     }
 
     /// Resolve query url
-    fn url(&self, path: &Path, force_dir: bool) -> String {
+    fn url(&self, path: &Path, force_dir: bool) -> RemoteResult<Url> {
         let mut p = self.url.clone();
         p.push_str(&self.path(path).to_string_lossy());
         if !p.ends_with('/') && (path.is_dir() || force_dir) {
             p.push('/');
         }
-        p
+        Url::parse(&p)
+            .map(|url| self.follow_moved(url))
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::BadAddress, e))
     }
 
     /// Resolve path
@@ -82,261 +930,2388 @@ impl WebDAVFs {
             Path::new(&self.wrkdir).join(path)
         }
     }
+
+    /// Get the path of `url` relative to the server base url
+    fn url_path(&self, url: &Url) -> Option<PathBuf> {
+        let base = Url::parse(&self.url).ok()?;
+        if base.origin() != url.origin() {
+            return None;
+        }
+        let path = url.path().strip_prefix(base.path().trim_end_matches('/'))?;
+        match path {
+            "" => Some(PathBuf::from("/")),
+            path if path.starts_with('/') => Some(PathBuf::from(path)),
+            _ => None,
+        }
+    }
+
+    /// List the resource at `url` and its children, returning the final url of the resource after redirects
+    fn list(&mut self, url: Url) -> RemoteResult<(Url, Listing)> {
+        let (url, listing, redirects) = self.list_depth(url, Depth::One)?;
+        for (from, to) in redirects {
+            self.rebase(&from, &to);
+        }
+
+        Ok((url, listing))
+    }
+
+    /// List the resource at `url` and its members up to `depth`, returning the final url of the resource
+    /// after redirects along with the permanent redirects which have been followed
+    fn list_depth(&self, url: Url, depth: Depth) -> RemoteResult<(Url, Listing, Redirects)> {
+        let (url, entries, redirects) = self.entries(url, depth)?;
+        let listing = entries.into_listing()?;
+        // the resource itself has been reported with an error status
+        if let (true, Some(entry)) = (listing.entries.is_empty(), listing.errors.first()) {
+            return Err(entry.error.clone());
+        }
+
+        Ok((url, listing, redirects))
+    }
+
+    /// List the resource at `url` and its members up to `depth` lazily, returning the final url of the
+    /// resource after redirects along with the permanent redirects which have been followed
+    fn entries(&self, url: Url, depth: Depth) -> RemoteResult<(Url, Entries, Redirects)> {
+        let body = Propfind::Prop(self.listing_prop())
+            .into_xml()
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        let (response, redirects) = self.dispatch(Request::propfind(url, depth, body))?;
+        let url = response.url().clone();
+        let entries = response
+            .entries()?
+            .principal_ids(self.principal_ids.clone());
+
+        Ok((url, entries, redirects))
+    }
+
+    /// List the resource at `path` lazily, returning the first entry, i.e. the resource itself, and the
+    /// entries of its children
+    fn open(&mut self, path: &Path, force_dir: bool) -> RemoteResult<(Entry, Entries)> {
+        let url = self.url(path, force_dir)?;
+        debug!("Listing directory: {}", url);
+        let (_, mut entries, redirects) = self.entries(url, Depth::One)?;
+        for (from, to) in redirects {
+            self.rebase(&from, &to);
+        }
+        match entries.next() {
+            Some(Ok(entry)) => Ok((entry, entries)),
+            Some(Err(err)) => Err(err),
+            // the resource itself has been reported with an error status
+            None => Err(entries
+                .errors()
+                .first()
+                .map(|entry| entry.error.clone())
+                .unwrap_or_else(|| RemoteError::new(RemoteErrorType::NoSuchFileOrDirectory))),
+        }
+    }
+
+    /// List the tree under the directory at `url` with a `Depth: 1` listing of each directory, listing the
+    /// directories of the same level in parallel.
+    ///
+    /// Directories which can't be listed are reported as entry errors, as a server would do in a
+    /// `Depth: infinity` listing.
+    fn walk_tree(&mut self, url: Url) -> RemoteResult<Listing> {
+        let (url, mut tree) = self.list(url)?;
+        let mut pending = Self::subdirectories(&url, tree.entries.get(1..).unwrap_or_default());
+        while !pending.is_empty() {
+            debug!("Listing {} directories", pending.len());
+            let workers = pending.len().min(TREE_WALK_WORKERS);
+            let mut batches = vec![Vec::new(); workers];
+            for (i, dir) in pending.drain(..).enumerate() {
+                batches[i % workers].push(dir);
+            }
+            let this = &*self;
+            let results: Vec<_> = std::thread::scope(|scope| {
+                let handles: Vec<_> = batches
+                    .into_iter()
+                    .map(|batch| {
+                        scope.spawn(move || {
+                            batch
+                                .into_iter()
+                                .map(|(path, url)| (path, this.list_depth(url, Depth::One)))
+                                .collect::<Vec<_>>()
+                        })
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .flat_map(|handle| handle.join().expect("tree walk worker panicked"))
+                    .collect()
+            });
+            for (path, result) in results {
+                match result {
+                    Ok((url, mut listing, redirects)) => {
+                        for (from, to) in redirects {
+                            self.rebase(&from, &to);
+                        }
+                        // the directory itself is already listed by its parent
+                        if !listing.entries.is_empty() {
+                            listing.entries.remove(0);
+                        }
+                        pending.extend(Self::subdirectories(&url, &listing.entries));
+                        tree.entries.append(&mut listing.entries);
+                        tree.errors.append(&mut listing.errors);
+                    }
+                    Err(error) => {
+                        debug!("could not list {}: {error}", path.display());
+                        tree.errors.push(EntryError { path, error });
+                    }
+                }
+            }
+        }
+
+        Ok(tree)
+    }
+
+    /// Returns whether the server runs DASL basic searches in the collection at `url`, as advertised by the
+    /// `DASL` header of an `OPTIONS` response; the answer is remembered until the next connection.
+    #[cfg(feature = "find")]
+    fn supports_search(&mut self, url: &Url) -> bool {
+        if let Some(dasl) = self.dasl {
+            return dasl;
+        }
+        let dasl = match self.send(Request::options(url.clone())) {
+            Ok(response) => response
+                .header("dasl")
+                .is_some_and(|grammars| grammars.contains("basicsearch")),
+            Err(err) => {
+                debug!("could not get capabilities of {url}: {err}");
+                false
+            }
+        };
+        self.dasl = Some(dasl);
+
+        dasl
+    }
+
+    /// Find the resources under `dir` whose name matches `pattern` with a DASL search, in the order of a
+    /// depth-first walk.
+    ///
+    /// The query only prefilters the resources on their display name, keeping every directory so that the tree
+    /// can be rebuilt, and `pattern` is then matched locally against the name in the href: a resource whose
+    /// display name differs from its name and doesn't match `pattern` is not found.
+    #[cfg(feature = "find")]
+    fn search_names(&mut self, dir: &Path, pattern: &str) -> RemoteResult<Vec<File>> {
+        let filter = Filter::displayname_like(&search::like_pattern(pattern)).or(Filter::is_dir());
+        let mut listing = self.search(SearchQuery::new(dir).filter(filter))?;
+        if let (true, Some(entry)) = (self.strict_listing, listing.errors.first()) {
+            return Err(entry.error.clone());
+        }
+        for entry in &listing.errors {
+            warn!("could not search {}: {}", entry.path.display(), entry.error);
+        }
+        // the scope may be left out of the results, while the tree is rooted at it
+        let root = self.path(dir);
+        if !listing.entries.iter().any(|entry| entry.file.path == root) {
+            listing.entries.insert(0, Entry::directory(root));
+        }
+        let tree = Tree::from_listing(listing).expect("the listing has a root");
+
+        Self::tree_names(&tree, &WildMatch::new(pattern))
+    }
+
+    /// Find the resources under `dir` whose name matches `filter` in a recursive listing, in the order of a
+    /// depth-first walk
+    #[cfg(feature = "find")]
+    fn list_names(&mut self, dir: &Path, filter: &WildMatch) -> RemoteResult<Vec<File>> {
+        let tree = self.list_tree(dir)?;
+
+        Self::tree_names(&tree, filter)
+    }
+
+    /// Get the resources of `tree` below its root whose name matches `filter`, in the order of a depth-first walk.
+    ///
+    /// A directory of the tree which could not be read fails the search, as in a walk with `list_dir`; resources
+    /// reported with an error status without being part of the tree are skipped instead, like children reported
+    /// with an error status by their parent.
+    #[cfg(feature = "find")]
+    fn tree_names(tree: &Tree, filter: &WildMatch) -> RemoteResult<Vec<File>> {
+        if let Some(error) = tree
+            .errors()
+            .find(|error| tree.iter().any(|entry| entry.file.path == error.path))
+        {
+            return Err(error.error.clone());
+        }
+
+        Ok(tree
+            .iter()
+            .skip(1)
+            .filter(|entry| filter.matches(&entry.file.name()))
+            .map(|entry| entry.file.clone())
+            .collect())
+    }
+
+    /// Get the path and url of the directories among `entries`, resolving their href against `url`
+    fn subdirectories(url: &Url, entries: &[Entry]) -> Vec<(PathBuf, Url)> {
+        entries
+            .iter()
+            .filter(|entry| entry.file.is_dir())
+            .filter_map(|entry| {
+                let href = entry.file.path.to_string_lossy();
+                let href = if href.ends_with('/') {
+                    href.to_string()
+                } else {
+                    format!("{href}/")
+                };
+                match url.join(&href) {
+                    Ok(url) => Some((entry.file.path.clone(), url)),
+                    Err(err) => {
+                        warn!("invalid href {href}: {err}");
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
 }
 
 impl RemoteFs for WebDAVFs {
     fn connect(&mut self) -> RemoteResult<Welcome> {
-        //self.list_dir(Path::new("/"))?;
+        self.client()?;
         self.connected = true;
+        self.dasl = None;
+        // servers without principals are still usable, so discovery never fails connecting
+        let banner = match self.current_principal() {
+            Ok(principal) => principal.and_then(|principal| principal.display_name),
+            Err(err) => {
+                debug!("could not get current principal: {err}");
+                None
+            }
+        };
+
+        Ok(Welcome::default().banner(banner))
+    }
+
+    fn disconnect(&mut self) -> RemoteResult<()> {
+        self.connected = false;
+        Ok(())
+    }
+
+    fn is_connected(&mut self) -> bool {
+        self.connected
+    }
+
+    fn pwd(&mut self) -> RemoteResult<PathBuf> {
+        Ok(PathBuf::from(&self.wrkdir))
+    }
+
+    fn change_dir(&mut self, dir: &Path) -> RemoteResult<PathBuf> {
+        let new_dir = self.path(dir);
+        let (url, listing) = self.list(self.url(&new_dir, true)?)?;
+        if listing.entries.is_empty() {
+            return Err(RemoteError::new(RemoteErrorType::NoSuchFileOrDirectory));
+        }
+        // follow the directory if it has been redirected
+        let new_dir = self.url_path(&url).unwrap_or(new_dir);
+
+        self.wrkdir = new_dir.to_string_lossy().to_string();
+        if !self.wrkdir.ends_with('/') {
+            self.wrkdir.push('/');
+        }
+        debug!("Changed directory to: {}", self.wrkdir);
+        Ok(new_dir)
+    }
+
+    fn list_dir(&mut self, path: &Path) -> RemoteResult<Vec<File>> {
+        let mut entries = self.list_dir_iter(path)?;
+        let files = entries
+            .by_ref()
+            .map(|entry| entry.map(|entry| entry.file))
+            .collect::<RemoteResult<Vec<_>>>()?;
+        for entry in entries.errors() {
+            warn!("could not list {}: {}", entry.path.display(), entry.error);
+        }
+
+        Ok(files)
+    }
+
+    /// Find the resources under the working directory whose name matches `search`, as the default
+    /// implementation does, without listing each directory.
+    ///
+    /// If the server advertises DASL, the resources are collected with a single `SEARCH` request prefiltering
+    /// their display name; otherwise the tree is listed with a single `Depth: infinity` listing. In both cases the
+    /// names are matched locally and the resources are returned in the order of a depth-first walk.
+    #[cfg(feature = "find")]
+    fn find(&mut self, search: &str) -> RemoteResult<Vec<File>> {
+        if !self.connected {
+            return Err(RemoteError::new(RemoteErrorType::NotConnected));
+        }
+        let dir = self.pwd()?;
+        let filter = WildMatch::new(search);
+        if self.supports_search(&self.url(&dir, true)?) {
+            match self.search_names(&dir, search) {
+                Err(err) if err.kind == RemoteErrorType::UnsupportedFeature => {
+                    debug!("search refused ({err}); listing the tree");
+                }
+                result => return result,
+            }
+        }
+
+        self.list_names(&dir, &filter)
+    }
+
+    fn stat(&mut self, path: &Path) -> RemoteResult<File> {
+        // the children are not read
+        self.open(path, false).map(|(entry, _)| entry.file)
+    }
+
+    fn setstat(&mut self, _path: &Path, _metadata: Metadata) -> RemoteResult<()> {
+        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+    }
+
+    fn exists(&mut self, path: &Path) -> RemoteResult<bool> {
+        debug!("Checking if file exists: {}", path.display());
+        Ok(self.stat(path).is_ok())
+    }
+
+    fn remove_file(&mut self, path: &Path) -> RemoteResult<()> {
+        let url = self.url(path, false)?;
+        debug!("Removing file: {}", url);
+        self.protected(path, |fs| fs.send(Request::delete(url))?.status())
+    }
+
+    fn remove_dir(&mut self, path: &Path) -> RemoteResult<()> {
+        let url = self.url(path, true)?;
+        debug!("Removing directory: {}", url);
+        self.send(Request::delete(url))?.status()
+    }
+
+    fn remove_dir_all(&mut self, path: &Path) -> RemoteResult<()> {
+        self.remove_dir(path)
+    }
+
+    fn create_dir(&mut self, path: &Path, _mode: UnixPex) -> RemoteResult<()> {
+        if self.stat(path).is_ok() {
+            return Err(RemoteError::new(RemoteErrorType::DirectoryAlreadyExists));
+        }
+        let url = self.url(path, true)?;
+        // check if dir exists
+        debug!("Creating directory: {}", url);
+        self.send(Request::mkcol(url))?.status()
+    }
+
+    fn symlink(&mut self, _path: &Path, _target: &Path) -> RemoteResult<()> {
+        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+    }
+
+    fn copy(&mut self, _src: &Path, _dest: &Path) -> RemoteResult<()> {
+        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+    }
+
+    fn mov(&mut self, src: &Path, dest: &Path) -> RemoteResult<()> {
+        let src_url = self.url(src, false)?;
+        let dest_url = self.url(dest, false)?;
+        debug!("Moving file: {} to {}", src_url, dest_url);
+
+        self.protected(src, |fs| {
+            fs.protected(dest, |fs| {
+                fs.send(Request::mv(src_url, &dest_url))?.status()
+            })
+        })
+    }
+
+    fn exec(&mut self, _cmd: &str) -> RemoteResult<(u32, String)> {
+        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+    }
+
+    fn append(&mut self, _path: &Path, _metadata: &Metadata) -> RemoteResult<WriteStream> {
+        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+    }
+
+    fn create(&mut self, _path: &Path, _metadata: &Metadata) -> RemoteResult<WriteStream> {
+        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+    }
+
+    fn open(&mut self, _path: &Path) -> RemoteResult<ReadStream> {
+        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+    }
+
+    fn create_file(
+        &mut self,
+        path: &Path,
+        _metadata: &Metadata,
+        mut reader: Box<dyn std::io::Read + Send>,
+    ) -> RemoteResult<u64> {
+        let url = self.url(path, false)?;
+        debug!("Creating file: {}", url);
+        let mut content = Vec::new();
+        reader
+            .read_to_end(&mut content)
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::IoError, e))?;
+        let size = content.len() as u64;
+        self.insufficient_storage = None;
+        let mut insufficient_storage = false;
+        let result = self.protected(path, |fs| {
+            let mut response = fs.send(Request::put(url, content.into()))?;
+            insufficient_storage = response.status_code() == 507;
+            response.status()
+        });
+        if let (Err(err), true) = (&result, insufficient_storage) {
+            let error = self.insufficient_storage(path, size);
+            debug!("{err}");
+            let kind = err.kind;
+            self.insufficient_storage = Some(error.clone());
+            return Err(RemoteError::new_ex(kind, error));
+        }
+        result?;
+
+        Ok(size)
+    }
+
+    fn open_file(&mut self, src: &Path, dest: Box<dyn std::io::Write + Send>) -> RemoteResult<u64> {
+        let url = self.url(src, false)?;
+        debug!("Opening file: {}", url);
+        self.download(url, dest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[cfg(feature = "with-containers")]
+    use std::io::Cursor;
+
+    use pretty_assertions::assert_eq;
+    #[cfg(feature = "with-containers")]
+    use serial_test::serial;
+
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::webdav_xml::elements::Properties;
+
+    const MULTISTATUS: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+        <D:multistatus xmlns:D="DAV:">
+        <D:response>
+        <D:href>/ciao/</D:href>
+        <D:propstat>
+        <D:prop><D:resourcetype><D:collection/></D:resourcetype></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/ciao/build.rs</D:href>
+        <D:propstat>
+        <D:prop><D:resourcetype/><D:getcontentlength>486</D:getcontentlength></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        </D:multistatus>"#;
+
+    #[test]
+    fn test_should_init_client() {
+        crate::mock::logger();
+        let client = WebDAVFs::new("user", "password", "http://localhost:3080");
+        assert_eq!(client.url, "http://localhost:3080");
+        assert_eq!(client.wrkdir, "/");
+    }
+
+    #[test]
+    fn test_should_get_url() {
+        let mut client = WebDAVFs::new("user", "password", "http://localhost:3080");
+        let path = Path::new("a.txt");
+        assert_eq!(
+            client.url(path, false).unwrap().as_str(),
+            "http://localhost:3080/a.txt"
+        );
+
+        let path = Path::new("/a.txt");
+        assert_eq!(
+            client.url(path, false).unwrap().as_str(),
+            "http://localhost:3080/a.txt"
+        );
+
+        let path = Path::new("/");
+        assert_eq!(
+            client.url(path, false).unwrap().as_str(),
+            "http://localhost:3080/"
+        );
+
+        client.wrkdir = "/test/".to_string();
+        let path = Path::new("a.txt");
+        assert_eq!(
+            client.url(path, false).unwrap().as_str(),
+            "http://localhost:3080/test/a.txt"
+        );
+
+        let path = Path::new("/a.txt");
+        assert_eq!(
+            client.url(path, false).unwrap().as_str(),
+            "http://localhost:3080/a.txt"
+        );
+
+        let path = Path::new("/gabibbo");
+        assert_eq!(
+            client.url(path, true).unwrap().as_str(),
+            "http://localhost:3080/gabibbo/"
+        );
+    }
+
+    #[test]
+    fn should_retry_transient_failures() {
+        crate::mock::logger();
+        let server = MockServer::scripted(vec![
+            MockResponse::new(503),
+            MockResponse::new(502),
+            MockResponse::new(207).body(MULTISTATUS),
+        ]);
+        let mut client = WebDAVFs::new("user", "password", &server.url()).retry_policy(
+            RetryPolicy::default().initial_backoff(std::time::Duration::from_millis(1)),
+        );
+        let files = client.list_dir(Path::new("/ciao/")).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[test]
+    fn should_give_up_retrying_after_max_attempts() {
+        crate::mock::logger();
+        let server = MockServer::scripted(vec![MockResponse::new(503)]);
+        let mut client = WebDAVFs::new("user", "password", &server.url()).retry_policy(
+            RetryPolicy::default()
+                .max_attempts(2)
+                .initial_backoff(std::time::Duration::from_millis(1)),
+        );
+        assert!(client.stat(Path::new("/ciao/")).is_err());
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn should_not_retry_non_idempotent_requests() {
+        crate::mock::logger();
+        let server = MockServer::scripted(vec![MockResponse::new(503), MockResponse::new(201)]);
+        let mut client = WebDAVFs::new("user", "password", &server.url()).retry_policy(
+            RetryPolicy::default().initial_backoff(std::time::Duration::from_millis(1)),
+        );
+        assert!(client
+            .mov(Path::new("/a.txt"), Path::new("/b.txt"))
+            .is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn should_not_retry_by_default() {
+        crate::mock::logger();
+        let server = MockServer::scripted(vec![MockResponse::new(503), MockResponse::new(204)]);
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        assert!(client.remove_file(Path::new("/a.txt")).is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn should_honour_retry_after() {
+        crate::mock::logger();
+        let server = MockServer::scripted(vec![
+            MockResponse::new(429).header("Retry-After", "0"),
+            MockResponse::new(201),
+        ]);
+        // the backoff would be waited if the header was ignored
+        let mut client = WebDAVFs::new("user", "password", &server.url()).retry_policy(
+            RetryPolicy::default()
+                .initial_backoff(std::time::Duration::from_secs(10))
+                .max_backoff(std::time::Duration::from_secs(10))
+                .jitter(false),
+        );
+        let started = std::time::Instant::now();
+        let reader = std::io::Cursor::new(b"test data\n".to_vec());
+        assert_eq!(
+            client
+                .create_file(Path::new("/a.txt"), &Metadata::default(), Box::new(reader))
+                .unwrap(),
+            10
+        );
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].method, "PUT");
+        assert_eq!(requests[1].path, "/a.txt");
+        assert_eq!(requests[1].body, b"test data\n");
+    }
+
+    #[test]
+    fn should_follow_redirect_for_collection() {
+        crate::mock::logger();
+        let server = MockServer::new(|req| match req.path.as_str() {
+            "/ciao" => MockResponse::new(301).header("Location", "/ciao/"),
+            _ => MockResponse::new(207).body(MULTISTATUS),
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        assert!(client.stat(Path::new("/ciao")).unwrap().is_dir());
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].method, "PROPFIND");
+        assert_eq!(requests[1].path, "/ciao/");
+        assert_eq!(requests[1].header("depth"), Some("1"));
+        assert_eq!(requests[1].body, requests[0].body);
+        assert!(requests[1].header("authorization").is_some());
+    }
+
+    #[test]
+    fn should_replay_put_body_on_redirect() {
+        crate::mock::logger();
+        let server = MockServer::new(|req| match req.path.as_str() {
+            "/a.txt" => MockResponse::new(307).header("Location", "/files/a.txt"),
+            _ => MockResponse::new(201),
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let reader = std::io::Cursor::new(b"test data\n".to_vec());
+        assert!(client
+            .create_file(Path::new("/a.txt"), &Metadata::default(), Box::new(reader))
+            .is_ok());
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].method, "PUT");
+        assert_eq!(requests[1].path, "/files/a.txt");
+        assert_eq!(requests[1].body, b"test data\n");
+    }
+
+    #[test]
+    fn should_drop_credentials_on_cross_origin_redirect() {
+        crate::mock::logger();
+        let other = MockServer::new(|_| MockResponse::new(200).body("hello"));
+        let location = format!("{}/a.txt", other.url());
+        let server = MockServer::new(move |_| MockResponse::new(302).header("Location", &location));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let buffer: Box<dyn std::io::Write + Send> = Box::new(Vec::new());
+        assert_eq!(client.open_file(Path::new("/a.txt"), buffer).unwrap(), 5);
+        assert!(server.requests()[0].header("authorization").is_some());
+        let requests = other.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].header("authorization").is_none());
+    }
+
+    #[test]
+    fn should_stop_after_redirect_hop_limit() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(302).header("Location", "/loop"));
+        let mut client = WebDAVFs::new("user", "password", &server.url())
+            .redirect_policy(RedirectPolicy::default().max_hops(3));
+        assert_eq!(
+            client.stat(Path::new("/a.txt")).unwrap_err().kind,
+            RemoteErrorType::ProtocolError
+        );
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[test]
+    fn should_not_follow_redirect_for_unlisted_method() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(301).header("Location", "/b.txt"));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        assert!(client.remove_file(Path::new("/a.txt")).is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn should_follow_redirected_directory_on_change_dir() {
+        crate::mock::logger();
+        let server = MockServer::new(|req| match req.path.as_str() {
+            "/old/" => MockResponse::new(301).header("Location", "/ciao/"),
+            _ => MockResponse::new(207).body(MULTISTATUS),
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        assert_eq!(
+            client.change_dir(Path::new("/old")).unwrap(),
+            PathBuf::from("/ciao")
+        );
+        assert_eq!(client.pwd().unwrap(), PathBuf::from("/ciao/"));
+    }
+
+    #[test]
+    fn should_redirect_members_of_moved_collection() {
+        crate::mock::logger();
+        let server = MockServer::new(|req| match req.path.as_str() {
+            "/dav/ciao/" => MockResponse::new(301).header("Location", "/webdav/ciao/"),
+            "/webdav/ciao/" => MockResponse::new(207).body(MULTISTATUS),
+            _ => MockResponse::new(204),
+        });
+        let mut client = WebDAVFs::new("user", "password", &format!("{}/dav", server.url()));
+        assert!(client.stat(Path::new("/ciao/")).is_ok());
+        assert!(client.remove_file(Path::new("/ciao/build.rs")).is_ok());
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2].method, "DELETE");
+        assert_eq!(requests[2].path, "/webdav/ciao/build.rs");
+    }
+
+    #[test]
+    fn should_rebase_url_on_permanent_redirect_of_base_collection() {
+        crate::mock::logger();
+        let server = MockServer::new(|req| match req.path.as_str() {
+            "/dav/" => MockResponse::new(301).header("Location", "/webdav/"),
+            "/webdav/" => MockResponse::new(207).body(MULTISTATUS),
+            _ => MockResponse::new(204),
+        });
+        let mut client = WebDAVFs::new("user", "password", &format!("{}/dav", server.url()));
+        assert!(client.stat(Path::new("/")).is_ok());
+        assert!(client.remove_file(Path::new("/ciao/build.rs")).is_ok());
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2].path, "/webdav/ciao/build.rs");
+    }
+
+    #[test]
+    fn should_remember_permanent_redirect_of_file_only() {
+        crate::mock::logger();
+        let server = MockServer::new(|req| match req.path.as_str() {
+            "/dav/a.txt" => MockResponse::new(301).header("Location", "/dav/archive/a.txt"),
+            "/dav/archive/a.txt" => MockResponse::new(200).body("moved"),
+            _ => MockResponse::new(204),
+        });
+        let mut client = WebDAVFs::new("user", "password", &format!("{}/dav", server.url()));
+        let open = |client: &mut WebDAVFs, path: &str| {
+            client
+                .open_file(Path::new(path), Box::new(std::io::sink()))
+                .unwrap()
+        };
+        assert_eq!(open(&mut client, "/a.txt"), 5);
+        // unrelated requests are not moved
+        assert!(client.remove_file(Path::new("/b.txt")).is_ok());
+        assert!(client.remove_file(Path::new("/a.txt.bak")).is_ok());
+        assert_eq!(open(&mut client, "/a.txt"), 5);
+
+        let paths: Vec<_> = server
+            .requests()
+            .iter()
+            .map(|req| format!("{} {}", req.method, req.path))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "GET /dav/a.txt",
+                "GET /dav/archive/a.txt",
+                "DELETE /dav/b.txt",
+                "DELETE /dav/a.txt.bak",
+                "GET /dav/archive/a.txt",
+            ]
+        );
+    }
+
+    const MULTISTATUS_WITH_ERRORS: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+        <D:multistatus xmlns:D="DAV:">
+        <D:response>
+        <D:href>/ciao/</D:href>
+        <D:propstat>
+        <D:prop><D:resourcetype><D:collection/></D:resourcetype></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/ciao/build.rs</D:href>
+        <D:propstat>
+        <D:prop><D:resourcetype/><D:getcontentlength>486</D:getcontentlength></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/ciao/private/</D:href>
+        <D:status>HTTP/1.1 403 Forbidden</D:status>
+        </D:response>
+        </D:multistatus>"#;
+
+    #[test]
+    fn should_list_dir_with_errors() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(207).body(MULTISTATUS_WITH_ERRORS));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let listing = client.list_dir_with_errors(Path::new("/ciao/")).unwrap();
+        assert_eq!(listing.entries.len(), 1);
+        assert_eq!(
+            listing.entries[0].file.path,
+            PathBuf::from("/ciao/build.rs")
+        );
+        assert_eq!(listing.errors.len(), 1);
+        assert_eq!(listing.errors[0].path, PathBuf::from("/ciao/private/"));
+        assert_eq!(
+            listing.errors[0].error.kind,
+            RemoteErrorType::CouldNotOpenFile
+        );
+        // not strict
+        assert_eq!(client.list_dir(Path::new("/ciao/")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn should_request_only_listing_properties() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(207).body(MULTISTATUS_WITH_ERRORS));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        client.list_dir(Path::new("/ciao/")).unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PROPFIND");
+        assert_eq!(requests[0].header("depth"), Some("1"));
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(!body.contains("allprop"));
+        for name in [
+            "resourcetype",
+            "getcontentlength",
+            "getlastmodified",
+            "creationdate",
+            "getetag",
+        ] {
+            assert!(body.contains(&format!(":{name}/>")), "{name} in {body}");
+        }
+        assert!(!body.contains("owner"));
+        assert!(!body.contains("executable"));
+    }
+
+    #[test]
+    fn should_request_extra_listing_properties() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(207).body(MULTISTATUS_WITH_ERRORS));
+        let mut client = WebDAVFs::new("user", "password", &server.url())
+            .listing_properties(
+                Properties::new()
+                    .with::<webdav_xml::properties::Executable>()
+                    .with::<webdav_xml::properties::ETag>(),
+            )
+            .principal_ids(|_| Some(1000));
+        client.list_dir(Path::new("/ciao/")).unwrap();
+
+        let requests = server.requests();
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains(":executable/>"));
+        assert!(body.contains(":owner/>"));
+        assert!(body.contains(":group/>"));
+        assert_eq!(body.matches("getetag").count(), 1);
+    }
+
+    #[test]
+    fn should_request_current_user_privileges() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(207).body(MULTISTATUS_WITH_ERRORS));
+        let mut client =
+            WebDAVFs::new("user", "password", &server.url()).current_user_privileges(true);
+        client.list_dir(Path::new("/ciao/")).unwrap();
+
+        let requests = server.requests();
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains(":current-user-privilege-set/>"));
+    }
+
+    /// Build a multistatus with a successful response for each of `hrefs` and an error for each of `errors`
+    fn multistatus(hrefs: &[&str], errors: &[&str]) -> String {
+        let mut body = String::from(r#"<?xml version="1.0"?><D:multistatus xmlns:D="DAV:">"#);
+        for href in hrefs {
+            let resourcetype = if href.ends_with('/') {
+                "<D:resourcetype><D:collection/></D:resourcetype>"
+            } else {
+                "<D:resourcetype/>"
+            };
+            body.push_str(&format!(
+                "<D:response><D:href>{href}</D:href><D:propstat><D:prop>{resourcetype}</D:prop>\
+                 <D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>"
+            ));
+        }
+        for href in errors {
+            body.push_str(&format!(
+                "<D:response><D:href>{href}</D:href><D:status>HTTP/1.1 403 Forbidden</D:status></D:response>"
+            ));
+        }
+        body.push_str("</D:multistatus>");
+        body
+    }
+
+    #[test]
+    fn should_list_tree_with_infinite_depth() {
+        crate::mock::logger();
+        let body = multistatus(
+            &[
+                "/docs/",
+                "/docs/2024/",
+                "/docs/readme.md",
+                "/docs/2024/report.odt",
+            ],
+            &["/docs/private/"],
+        );
+        let server = MockServer::new(move |_| MockResponse::new(207).body(body.clone()));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let tree = client.list_tree(Path::new("/docs")).unwrap();
+
+        assert_eq!(tree.entry.file.path, PathBuf::from("/docs/"));
+        assert_eq!(
+            tree.iter()
+                .map(|entry| entry.file.path.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("/docs/"),
+                PathBuf::from("/docs/2024/"),
+                PathBuf::from("/docs/2024/report.odt"),
+                PathBuf::from("/docs/readme.md"),
+            ]
+        );
+        assert_eq!(tree.errors.len(), 1);
+        assert_eq!(tree.errors[0].path, PathBuf::from("/docs/private/"));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/docs/");
+        assert_eq!(requests[0].header("depth"), Some("infinity"));
+    }
+
+    #[test]
+    fn should_walk_tree_when_infinite_depth_is_refused() {
+        crate::mock::logger();
+        let server = MockServer::new(|req| {
+            if req.header("depth") == Some("infinity") {
+                return MockResponse::new(403)
+                    .header("Content-Type", "application/xml; charset=utf-8")
+                    .body(
+                        r#"<?xml version="1.0"?>
+                    <D:error xmlns:D="DAV:"><D:propfind-finite-depth/></D:error>"#,
+                    );
+            }
+            match req.path.as_str() {
+                "/docs/" => MockResponse::new(207).body(multistatus(
+                    &["/docs/", "/docs/2023/", "/docs/2024/", "/docs/readme.md"],
+                    &[],
+                )),
+                "/docs/2023/" => MockResponse::new(207)
+                    .body(multistatus(&["/docs/2023/", "/docs/2023/march/"], &[])),
+                "/docs/2023/march/" => MockResponse::new(207).body(multistatus(
+                    &["/docs/2023/march/", "/docs/2023/march/report.odt"],
+                    &[],
+                )),
+                _ => MockResponse::new(403),
+            }
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let tree = client.list_tree(Path::new("/docs/")).unwrap();
+
+        let mut paths: Vec<_> = tree.iter().map(|entry| entry.file.path.clone()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/docs/"),
+                PathBuf::from("/docs/2023/"),
+                PathBuf::from("/docs/2023/march/"),
+                PathBuf::from("/docs/2023/march/report.odt"),
+                PathBuf::from("/docs/2024/"),
+                PathBuf::from("/docs/readme.md"),
+            ]
+        );
+        // the unreadable directory is reported as an error of its parent
+        let errors: Vec<_> = tree.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, PathBuf::from("/docs/2024/"));
+        assert_eq!(errors[0].error.kind, RemoteErrorType::CouldNotOpenFile);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 5);
+        assert!(requests[1..]
+            .iter()
+            .all(|req| req.header("depth") == Some("1")));
+
+        // strict listings fail
+        let mut client = WebDAVFs::new("user", "password", &server.url()).strict_listing(true);
+        let err = client.list_tree(Path::new("/docs/")).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::CouldNotOpenFile);
+    }
+
+    #[test]
+    fn should_list_dir_lazily() {
+        crate::mock::logger();
+        let mut hrefs = vec![String::from("/huge/")];
+        hrefs.extend((0..2000).map(|i| format!("/huge/file-{i}.txt")));
+        let hrefs: Vec<&str> = hrefs.iter().map(String::as_str).collect();
+        let body = multistatus(&hrefs, &[]);
+        let server = MockServer::new(move |_| MockResponse::new(207).body(body.clone()));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+
+        let first: Vec<_> = client
+            .list_dir_iter(Path::new("/huge/"))
+            .unwrap()
+            .take(2)
+            .map(|entry| entry.unwrap().file.path)
+            .collect();
+        assert_eq!(
+            first,
+            vec![
+                PathBuf::from("/huge/file-0.txt"),
+                PathBuf::from("/huge/file-1.txt")
+            ]
+        );
+        // the dropped listing doesn't prevent further requests
+        assert_eq!(client.list_dir(Path::new("/huge/")).unwrap().len(), 2000);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn should_list_dir_lazily_with_errors() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(207).body(MULTISTATUS_WITH_ERRORS));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let mut entries = client.list_dir_iter(Path::new("/ciao/")).unwrap();
+        assert_eq!(
+            entries.next().unwrap().unwrap().file.path,
+            PathBuf::from("/ciao/build.rs")
+        );
+        assert!(entries.next().is_none());
+        assert_eq!(entries.errors().len(), 1);
+        assert_eq!(entries.errors()[0].path, PathBuf::from("/ciao/private/"));
+
+        let mut client = WebDAVFs::new("user", "password", &server.url()).strict_listing(true);
+        let mut entries = client.list_dir_iter(Path::new("/ciao/")).unwrap();
+        assert!(entries.next().unwrap().is_ok());
+        assert_eq!(
+            entries.next().unwrap().unwrap_err().kind,
+            RemoteErrorType::CouldNotOpenFile
+        );
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn should_fail_strict_listing_with_errors() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(207).body(MULTISTATUS_WITH_ERRORS));
+        let mut client = WebDAVFs::new("user", "password", &server.url()).strict_listing(true);
+        let err = client.list_dir(Path::new("/ciao/")).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::CouldNotOpenFile);
+        assert_eq!(
+            err.msg.unwrap(),
+            "PROPFIND /ciao/private/ returned 403 Forbidden"
+        );
+    }
+
+    #[test]
+    fn should_propfind_selected_properties() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| {
+            MockResponse::new(207).body(
+                r#"<?xml version="1.0"?>
+                <d:multistatus xmlns:d="DAV:" xmlns:oc="http://owncloud.org/ns">
+                <d:response>
+                <d:href>/docs/report.odt</d:href>
+                <d:propstat>
+                <d:prop><oc:fileid>42</oc:fileid><d:quota-used-bytes>1024</d:quota-used-bytes></d:prop>
+                <d:status>HTTP/1.1 200 OK</d:status>
+                </d:propstat>
+                </d:response>
+                </d:multistatus>"#,
+            )
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let prop = Properties::new()
+            .with_name("http://owncloud.org/ns", "oc", "fileid")
+            .with_name("DAV:", "d", "quota-used-bytes");
+        let multistatus = client
+            .propfind(
+                Path::new("/docs/report.odt"),
+                Depth::Zero,
+                Propfind::Prop(prop),
+            )
+            .unwrap();
+
+        assert_eq!(multistatus.response.len(), 1);
+        let webdav_xml::elements::Response::Propstat { propstat, .. } = &multistatus.response[0]
+        else {
+            panic!("expected propstat response");
+        };
+        assert_eq!(
+            propstat.head.prop.value("http://owncloud.org/ns", "fileid"),
+            Some(&webdav_xml::Value::Text("42".into()))
+        );
+        assert_eq!(
+            propstat.head.prop.value("DAV:", "quota-used-bytes"),
+            Some(&webdav_xml::Value::Text("1024".into()))
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PROPFIND");
+        assert_eq!(requests[0].header("depth"), Some("0"));
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("propfind"));
+        assert!(body.contains("<oc:fileid/>"));
+        assert!(body.contains(r#"xmlns:oc="http://owncloud.org/ns""#));
+    }
+
+    #[test]
+    fn should_proppatch_dead_properties() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| {
+            MockResponse::new(207).body(
+                r#"<?xml version="1.0"?>
+                <d:multistatus xmlns:d="DAV:" xmlns:ex="http://example.com/ns">
+                <d:response>
+                <d:href>/docs/report.odt</d:href>
+                <d:propstat>
+                <d:prop><ex:color/></d:prop>
+                <d:status>HTTP/1.1 424 Failed Dependency</d:status>
+                </d:propstat>
+                <d:propstat>
+                <d:prop><d:getetag/></d:prop>
+                <d:status>HTTP/1.1 403 Forbidden</d:status>
+                <d:error><d:cannot-modify-protected-property/></d:error>
+                </d:propstat>
+                </d:response>
+                </d:multistatus>"#,
+            )
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let set = Properties::new()
+            .with_name_value("http://example.com/ns", "ex", "color", "red")
+            .with_name_value("DAV:", "d", "getetag", "\"1\"");
+        let remove = Properties::new().with_name("http://example.com/ns", "ex", "shape");
+        let results = client
+            .proppatch(Path::new("/docs/report.odt"), set, remove)
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0].namespace.as_deref(),
+            Some("http://example.com/ns")
+        );
+        assert_eq!(results[0].name, "color");
+        assert_eq!(
+            results[0].result.as_ref().unwrap_err().kind,
+            RemoteErrorType::ProtocolError
+        );
+        assert_eq!(results[1].namespace.as_deref(), Some("DAV:"));
+        assert_eq!(results[1].name, "getetag");
+        assert_eq!(
+            results[1].result.as_ref().unwrap_err().kind,
+            RemoteErrorType::PexError
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PROPPATCH");
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("propertyupdate"));
+        assert!(body.contains("<ex:color>red</ex:color>"));
+        assert!(body.contains("<ex:shape/>"));
+        assert!(body.find(":set>").unwrap() < body.find(":remove>").unwrap());
+        assert!(body.contains(r#"xmlns:ex="http://example.com/ns""#));
+    }
+
+    #[test]
+    fn should_report_all_properties_patched_without_multistatus() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(200));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let set = Properties::new().with_name_value("http://example.com/ns", "ex", "color", "red");
+        let results = client
+            .proppatch(Path::new("/docs/report.odt"), set, Properties::new())
+            .unwrap();
+
+        assert_eq!(
+            results,
+            vec![PropertyResult {
+                namespace: Some(String::from("http://example.com/ns")),
+                name: String::from("color"),
+                result: Ok(()),
+            }]
+        );
+        let body = String::from_utf8_lossy(&server.requests()[0].body).to_string();
+        assert!(!body.contains("remove"));
+    }
+
+    #[test]
+    fn should_search() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(207).body(MULTISTATUS_WITH_ERRORS));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let query = SearchQuery::new(Path::new("/ciao/"))
+            .filter(Filter::size_gt(100))
+            .order_by(SearchProperty::Size, false)
+            .limit(5);
+        let listing = client.search(query).unwrap();
+        // the scope itself matched the query
+        assert_eq!(listing.entries[0].file.path, PathBuf::from("/ciao/"));
+        assert_eq!(
+            listing.entries[1..],
+            client
+                .list_dir_with_errors(Path::new("/ciao/"))
+                .map(|listing| listing.entries)
+                .unwrap()
+        );
+        assert_eq!(listing.errors.len(), 1);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "SEARCH");
+        assert_eq!(requests[0].path, "/ciao/");
+        let body: String = String::from_utf8_lossy(&requests[0].body)
+            .split_whitespace()
+            .collect();
+        assert!(body.contains(&format!(
+            "<d:scope><d:href>{}/ciao/</d:href><d:depth>infinity</d:depth></d:scope>",
+            server.url()
+        )));
+        assert!(body.contains("<d:literal>100</d:literal>"));
+        assert!(body.contains("<d:ascending/>"));
+        assert!(body.contains("<d:nresults>5</d:nresults>"));
+        for name in ["resourcetype", "getlastmodified", "getetag"] {
+            assert!(body.contains(&format!(":{name}/>")), "{name} in {body}");
+        }
+    }
+
+    #[test]
+    fn should_fail_search_on_server_without_dasl() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(501));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let err = client
+            .search(SearchQuery::new(Path::new("/ciao/")))
+            .unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::UnsupportedFeature);
+    }
+
+    /// Tree of the server used by the `find` tests, with the display name of each resource, in the order of
+    /// its listings; siblings are not sorted and some display names differ from the name in the href.
+    #[cfg(feature = "find")]
+    const FIND_TREE: &[(&str, &str)] = &[
+        ("/docs/", "docs"),
+        ("/docs/readme.md", "readme.md"),
+        ("/docs/2024/", "2024"),
+        ("/docs/2024/report.md", "report.md"),
+        ("/docs/2024/notes.txt", "notes.md"),
+        ("/docs/2024/march/", "march"),
+        ("/docs/2024/march/report.md", "report.md"),
+        ("/docs/archive.md", "archive.md"),
+    ];
+
+    /// Build the multistatus reporting the resource type and display name of `entries`
+    #[cfg(feature = "find")]
+    fn find_multistatus<'a>(entries: impl Iterator<Item = &'a (&'a str, &'a str)>) -> String {
+        let mut body = String::from(r#"<?xml version="1.0"?><D:multistatus xmlns:D="DAV:">"#);
+        for (href, name) in entries {
+            let resourcetype = if href.ends_with('/') {
+                "<D:resourcetype><D:collection/></D:resourcetype>"
+            } else {
+                "<D:resourcetype/>"
+            };
+            body.push_str(&format!(
+                "<D:response><D:href>{href}</D:href><D:propstat><D:prop>{resourcetype}\
+                 <D:displayname>{name}</D:displayname></D:prop>\
+                 <D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>"
+            ));
+        }
+        body.push_str("</D:multistatus>");
+        body
+    }
+
+    /// Start a server serving `FIND_TREE`, advertising DASL if `dasl` is set. With `finite_depth`, the server
+    /// refuses infinite depth listings and `/docs/2024/march/` can't be listed or searched.
+    #[cfg(feature = "find")]
+    fn find_server(dasl: bool, finite_depth: bool) -> MockServer {
+        MockServer::new(move |req| match req.method.as_str() {
+            "OPTIONS" if dasl => MockResponse::new(200).header("DASL", "<DAV:basicsearch>"),
+            "OPTIONS" => MockResponse::new(200),
+            // the scope and the resources under it whose display name is `like` the pattern of the query, if any,
+            // or which are collections if the query asks for them; the deepest first, siblings being kept in order
+            "SEARCH" => {
+                let body: String = String::from_utf8_lossy(&req.body)
+                    .split_whitespace()
+                    .collect();
+                let collections = body.contains("<d:is-collection/>");
+                let like = body
+                    .split_once("<d:prop><d:displayname/></d:prop><d:literal>")
+                    .and_then(|(_, rest)| rest.split_once("</d:literal>"))
+                    .map(|(pattern, _)| {
+                        WildMatch::new(&pattern.replace('%', "*").replace('_', "?"))
+                    });
+                let mut entries: Vec<_> = FIND_TREE
+                    .iter()
+                    .filter(|(href, name)| {
+                        href.starts_with(req.path.as_str())
+                            && !(finite_depth
+                                && href
+                                    .strip_prefix("/docs/2024/march/")
+                                    .is_some_and(|rest| !rest.is_empty()))
+                            && (like.as_ref().is_none_or(|like| like.matches(name))
+                                || (collections && href.ends_with('/')))
+                    })
+                    .collect();
+                entries.sort_by_key(|(href, _)| {
+                    std::cmp::Reverse(href.trim_end_matches('/').matches('/').count())
+                });
+                let body = find_multistatus(entries.into_iter());
+                if finite_depth {
+                    return MockResponse::new(207).body(body.replace(
+                        "</D:multistatus>",
+                        "<D:response><D:href>/docs/2024/march/</D:href>\
+                         <D:status>HTTP/1.1 403 Forbidden</D:status></D:response></D:multistatus>",
+                    ));
+                }
+                MockResponse::new(207).body(body)
+            }
+            _ if finite_depth && req.header("depth") == Some("infinity") => MockResponse::new(403)
+                .header("Content-Type", "application/xml; charset=utf-8")
+                .body(
+                    r#"<?xml version="1.0"?>
+                    <D:error xmlns:D="DAV:"><D:propfind-finite-depth/></D:error>"#,
+                ),
+            _ if finite_depth && req.path == "/docs/2024/march/" => MockResponse::new(403),
+            _ => {
+                let infinite = req.header("depth") == Some("infinity");
+                let entries = FIND_TREE.iter().filter(|(href, _)| {
+                    match href.strip_prefix(req.path.as_str()) {
+                        Some(rest) => infinite || !rest.trim_end_matches('/').contains('/'),
+                        None => false,
+                    }
+                });
+                MockResponse::new(207).body(find_multistatus(entries))
+            }
+        })
+    }
+
+    /// Connect a client to `server` in `/docs/`, returning the result of the default `find` implementation for
+    /// each of `patterns`
+    #[cfg(feature = "find")]
+    fn walk_find(
+        server: &MockServer,
+        patterns: &[&str],
+    ) -> (WebDAVFs, Vec<RemoteResult<Vec<File>>>) {
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        client.connect().unwrap();
+        client.change_dir(Path::new("/docs/")).unwrap();
+        let expected = patterns
+            .iter()
+            .map(|pattern| client.iter_search(Path::new("/docs/"), &WildMatch::new(pattern)))
+            .collect();
+        (client, expected)
+    }
+
+    #[test]
+    #[cfg(feature = "find")]
+    fn should_find_with_search() {
+        crate::mock::logger();
+        let server = find_server(true, false);
+        let (mut client, expected) = walk_find(&server, &["*.md", "re?dme.*", "2024"]);
+        let requests = server.requests().len();
+        let found = client.find("*.md").unwrap();
+        assert_eq!(&found, expected[0].as_ref().unwrap());
+        // names are taken from the href, not the display name, in the order of a walk although the server
+        // returns them in another order
+        assert_eq!(
+            found
+                .iter()
+                .map(|file| file.path.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("/docs/readme.md"),
+                PathBuf::from("/docs/2024/report.md"),
+                PathBuf::from("/docs/2024/march/report.md"),
+                PathBuf::from("/docs/archive.md"),
+            ]
+        );
+        assert_eq!(
+            &client.find("re?dme.*").unwrap(),
+            expected[1].as_ref().unwrap()
+        );
+        assert_eq!(&client.find("2024").unwrap(), expected[2].as_ref().unwrap());
+
+        let requests = &server.requests()[requests..];
+        let methods: Vec<_> = requests.iter().map(|req| req.method.as_str()).collect();
+        // capabilities are only asked once
+        assert_eq!(methods, vec!["OPTIONS", "SEARCH", "SEARCH", "SEARCH"]);
+        let body: String = String::from_utf8_lossy(&requests[1].body)
+            .split_whitespace()
+            .collect();
+        assert!(body.contains("<d:like>"));
+        assert!(body.contains("<d:literal>%.md</d:literal>"));
+        assert!(body.contains("<d:is-collection/>"));
+        assert!(body.contains("<d:depth>infinity</d:depth>"));
+
+        // searching the display names only would select other resources
+        let listing = client
+            .search(SearchQuery::new(Path::new("/docs/")).filter(Filter::displayname_like("%.md")))
+            .unwrap();
+        let paths: Vec<_> = listing
+            .entries
+            .iter()
+            .map(|entry| entry.file.path.clone())
+            .collect();
+        assert!(paths.contains(&PathBuf::from("/docs/2024/notes.txt")));
+        assert!(!paths.contains(&PathBuf::from("/docs/2024/")));
+    }
+
+    #[test]
+    #[cfg(feature = "find")]
+    fn should_fail_search_on_unreadable_directory() {
+        crate::mock::logger();
+        let server = find_server(true, true);
+        let (mut client, expected) = walk_find(&server, &["*.md"]);
+        // `/docs/2024/march/` can't be read, which fails the walk too
+        let expected = expected[0].as_ref().unwrap_err();
+        let err = client.find("*.md").unwrap_err();
+        assert_eq!(err.kind, expected.kind);
+    }
+
+    #[test]
+    #[cfg(feature = "find")]
+    fn should_find_with_infinite_depth_listing() {
+        crate::mock::logger();
+        let server = find_server(false, false);
+        let patterns = ["*.md", "*", "2024", "*.odt"];
+        let (mut client, expected) = walk_find(&server, &patterns);
+        for (pattern, expected) in patterns.iter().zip(expected) {
+            let requests = server.requests().len();
+            assert_eq!(
+                client.find(pattern).unwrap(),
+                expected.unwrap(),
+                "{pattern}"
+            );
+            let requests = &server.requests()[requests..];
+            assert!(requests
+                .iter()
+                .all(|req| req.method == "OPTIONS" || req.header("depth") == Some("infinity")));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "find")]
+    fn should_find_walking_tree_when_infinite_depth_is_refused() {
+        crate::mock::logger();
+        let server = find_server(false, true);
+        let (mut client, expected) = walk_find(&server, &["*.txt"]);
+        // `/docs/2024/march/` can't be listed
+        let expected = expected[0].as_ref().unwrap_err();
+        let err = client.find("*.txt").unwrap_err();
+        assert_eq!(err.kind, expected.kind);
+    }
+
+    #[test]
+    fn should_sync_collection() {
+        crate::mock::logger();
+        let server = MockServer::scripted(vec![
+            MockResponse::new(207).body(
+                r#"<?xml version="1.0"?>
+                <d:multistatus xmlns:d="DAV:">
+                <d:response>
+                <d:href>/docs/readme.md</d:href>
+                <d:propstat>
+                <d:prop><d:resourcetype/><d:getcontentlength>42</d:getcontentlength></d:prop>
+                <d:status>HTTP/1.1 200 OK</d:status>
+                </d:propstat>
+                </d:response>
+                <d:sync-token>http://example.com/sync/1</d:sync-token>
+                </d:multistatus>"#,
+            ),
+            MockResponse::new(207).body(
+                r#"<?xml version="1.0"?>
+                <d:multistatus xmlns:d="DAV:">
+                <d:response>
+                <d:href>/docs/readme.md</d:href>
+                <d:status>HTTP/1.1 404 Not Found</d:status>
+                </d:response>
+                <d:sync-token>http://example.com/sync/2</d:sync-token>
+                </d:multistatus>"#,
+            ),
+        ]);
+        let mut client = WebDAVFs::new("user", "password", &server.url())
+            .sync_level(SyncLevel::Infinite)
+            .sync_limit(100);
+        let SyncResult::Changes(changes) =
+            client.sync_collection(Path::new("/docs/"), None).unwrap()
+        else {
+            panic!("resync required");
+        };
+        assert_eq!(changes.entries.len(), 1);
+        assert_eq!(changes.entries[0].file.metadata.size, 42);
+        assert!(changes.removed.is_empty());
+        assert_eq!(changes.token, "http://example.com/sync/1");
+        assert!(!changes.truncated);
+
+        let SyncResult::Changes(changes) = client
+            .sync_collection(Path::new("/docs/"), Some(&changes.token))
+            .unwrap()
+        else {
+            panic!("resync required");
+        };
+        assert!(changes.entries.is_empty());
+        assert_eq!(changes.removed, vec![PathBuf::from("/docs/readme.md")]);
+        assert_eq!(changes.token, "http://example.com/sync/2");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "REPORT");
+        assert_eq!(requests[0].path, "/docs/");
+        assert_eq!(requests[0].header("depth"), Some("0"));
+        let body: String = String::from_utf8_lossy(&requests[0].body)
+            .split_whitespace()
+            .collect();
+        assert!(body.contains("<d:sync-collectionxmlns:d=\"DAV:\"><d:sync-token/>"));
+        assert!(body.contains("<d:sync-level>infinite</d:sync-level>"));
+        assert!(body.contains("<d:nresults>100</d:nresults>"));
+        assert!(body.contains(":getlastmodified/>"));
+        let body = String::from_utf8_lossy(&requests[1].body);
+        assert!(body.contains("<d:sync-token>http://example.com/sync/1</d:sync-token>"));
+    }
+
+    #[test]
+    fn should_require_resync_on_invalid_sync_token() {
+        crate::mock::logger();
+        let server = MockServer::scripted(vec![
+            MockResponse::new(403)
+                .header("Content-Type", "application/xml; charset=utf-8")
+                .body(
+                    r#"<?xml version="1.0"?>
+                <d:error xmlns:d="DAV:"><d:valid-sync-token/></d:error>"#,
+                ),
+            MockResponse::new(403),
+        ]);
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        assert_eq!(
+            client
+                .sync_collection(Path::new("/docs/"), Some("http://example.com/sync/1"))
+                .unwrap(),
+            SyncResult::ResyncRequired
+        );
+        // other errors are not a resync
+        let err = client
+            .sync_collection(Path::new("/docs/"), Some("http://example.com/sync/1"))
+            .unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::CouldNotOpenFile);
+    }
+
+    /// Build the multistatus of a quota propfind
+    fn quota_response(available: &str, used: &str) -> String {
+        format!(
+            r#"<?xml version="1.0"?>
+            <d:multistatus xmlns:d="DAV:">
+            <d:response>
+            <d:href>/docs/</d:href>
+            <d:propstat>
+            <d:prop><d:quota-available-bytes>{available}</d:quota-available-bytes>{used}</d:prop>
+            <d:status>HTTP/1.1 200 OK</d:status>
+            </d:propstat>
+            <d:propstat>
+            <d:prop><d:quota-used-bytes/></d:prop>
+            <d:status>HTTP/1.1 404 Not Found</d:status>
+            </d:propstat>
+            </d:response>
+            </d:multistatus>"#
+        )
+    }
+
+    #[test]
+    fn should_get_quota() {
+        crate::mock::logger();
+        let server = MockServer::scripted(vec![
+            MockResponse::new(207).body(quota_response(
+                "-3",
+                "<d:quota-used-bytes>4096</d:quota-used-bytes>",
+            )),
+            MockResponse::new(207).body(quota_response("1024", "")),
+        ]);
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        assert_eq!(
+            client.quota(Path::new("/docs/")).unwrap(),
+            Quota {
+                available: QuotaBytes::Unlimited,
+                used: QuotaBytes::Bytes(4096),
+            }
+        );
+        // used bytes are not reported
+        assert_eq!(
+            client.quota(Path::new("/docs/")).unwrap(),
+            Quota {
+                available: QuotaBytes::Bytes(1024),
+                used: QuotaBytes::Unknown,
+            }
+        );
 
-        Ok(Welcome::default())
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PROPFIND");
+        assert_eq!(requests[0].header("depth"), Some("0"));
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains(":quota-available-bytes/>"));
+        assert!(body.contains(":quota-used-bytes/>"));
     }
 
-    fn disconnect(&mut self) -> RemoteResult<()> {
-        self.connected = false;
-        Ok(())
-    }
+    #[test]
+    fn should_report_quota_on_insufficient_storage() {
+        crate::mock::logger();
+        let server = MockServer::new(|req| match req.method.as_str() {
+            "PUT" if req.path == "/docs/a.txt" => MockResponse::new(507),
+            "PUT" if req.path == "/docs/c.txt" => MockResponse::new(500),
+            "PUT" => MockResponse::new(201),
+            _ => MockResponse::new(207).body(quota_response(
+                "100",
+                "<d:quota-used-bytes>2048</d:quota-used-bytes>",
+            )),
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        assert!(client.last_insufficient_storage().is_none());
+        let reader = std::io::Cursor::new(b"test data\n".to_vec());
+        let err = client
+            .create_file(
+                Path::new("/docs/a.txt"),
+                &Metadata::default(),
+                Box::new(reader),
+            )
+            .unwrap_err();
 
-    fn is_connected(&mut self) -> bool {
-        self.connected
-    }
+        assert_eq!(err.kind, RemoteErrorType::IoError);
+        assert_eq!(
+            err.msg.unwrap(),
+            "insufficient storage: cannot write 10 bytes to /docs/a.txt \
+             (2048 bytes used, 100 bytes available)"
+        );
+        let error = client.last_insufficient_storage().unwrap();
+        assert_eq!(error.path, PathBuf::from("/docs/a.txt"));
+        assert_eq!(error.size, 10);
+        assert_eq!(
+            error.quota,
+            Some(Quota {
+                available: QuotaBytes::Bytes(100),
+                used: QuotaBytes::Bytes(2048),
+            })
+        );
+        let requests = server.requests();
+        assert_eq!(requests[1].method, "PROPFIND");
+        assert_eq!(requests[1].path, "/docs");
 
-    fn pwd(&mut self) -> RemoteResult<PathBuf> {
-        Ok(PathBuf::from(&self.wrkdir))
+        // the next write resets it, whether it succeeds or fails for another reason
+        let reader = std::io::Cursor::new(b"test data\n".to_vec());
+        client
+            .create_file(
+                Path::new("/docs/b.txt"),
+                &Metadata::default(),
+                Box::new(reader),
+            )
+            .unwrap();
+        assert!(client.last_insufficient_storage().is_none());
+        let reader = std::io::Cursor::new(b"test data\n".to_vec());
+        client
+            .create_file(
+                Path::new("/docs/a.txt"),
+                &Metadata::default(),
+                Box::new(reader),
+            )
+            .unwrap_err();
+        assert!(client.last_insufficient_storage().is_some());
+        let reader = std::io::Cursor::new(b"test data\n".to_vec());
+        client
+            .create_file(
+                Path::new("/docs/c.txt"),
+                &Metadata::default(),
+                Box::new(reader),
+            )
+            .unwrap_err();
+        assert!(client.last_insufficient_storage().is_none());
     }
 
-    fn change_dir(&mut self, dir: &Path) -> RemoteResult<PathBuf> {
-        let new_dir = self.path(dir);
-        self.list_dir(&new_dir)?;
-
-        self.wrkdir = new_dir.to_string_lossy().to_string();
-        if !self.wrkdir.ends_with('/') {
-            self.wrkdir.push('/');
-        }
-        debug!("Changed directory to: {}", self.wrkdir);
-        Ok(new_dir)
+    /// Build the multistatus of a depth 0 propfind of `href` reporting `props`
+    fn prop_response(href: &str, props: &str) -> String {
+        format!(
+            r#"<?xml version="1.0"?>
+            <d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+            <d:response>
+            <d:href>{href}</d:href>
+            <d:propstat>
+            <d:prop>{props}</d:prop>
+            <d:status>HTTP/1.1 200 OK</d:status>
+            </d:propstat>
+            </d:response>
+            </d:multistatus>"#
+        )
     }
 
-    fn list_dir(&mut self, path: &Path) -> RemoteResult<Vec<File>> {
-        let url = self.url(path, true);
-        debug!("Listing directory: {}", url);
-        let response = self
-            .client
-            .list(&url, "1")
-            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
-
-        debug!("Parsing response");
-        match ResponseParser::from(response).files()? {
-            files if !files.is_empty() => {
-                // remove file at 0
-                let mut children = Vec::with_capacity(files.len());
-                for file in files.iter().skip(1) {
-                    children.push(file.clone());
-                }
-                Ok(children)
+    /// Mock a server reporting the principal at `/principals/alice/` with `props`
+    fn principal_server(props: &'static str) -> MockServer {
+        MockServer::new(move |req| match req.path.as_str() {
+            "/" => MockResponse::new(207).body(prop_response(
+                "/",
+                "<d:current-user-principal><d:href>/principals/alice/</d:href>\
+                 </d:current-user-principal>",
+            )),
+            "/principals/alice/" => {
+                MockResponse::new(207).body(prop_response("/principals/alice/", props))
             }
-            _ => Err(RemoteError::new(RemoteErrorType::NoSuchFileOrDirectory)),
-        }
+            _ => MockResponse::new(404),
+        })
     }
 
-    fn stat(&mut self, path: &Path) -> RemoteResult<File> {
-        let url = self.url(path, false);
-        debug!("Listing directory: {}", url);
-        let response = self
-            .client
-            .list(&url, "1")
-            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+    #[test]
+    fn should_get_current_principal() {
+        crate::mock::logger();
+        let server = principal_server(
+            "<d:displayname>Alice</d:displayname>\
+             <d:principal-URL><d:href>/principals/users/alice/</d:href></d:principal-URL>\
+             <cal:calendar-home-set><d:href>/calendars/alice/</d:href></cal:calendar-home-set>",
+        );
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let principal = client.current_principal().unwrap().unwrap();
 
-        debug!("Parsing response");
-        match ResponseParser::from(response).files()? {
-            files if !files.is_empty() => Ok(files[0].clone()),
-            _ => Err(RemoteError::new(RemoteErrorType::NoSuchFileOrDirectory)),
+        let url = |path: &str| Url::parse(&server.url()).unwrap().join(path).unwrap();
+        assert_eq!(principal.url, url("/principals/alice/"));
+        assert_eq!(principal.display_name.as_deref(), Some("Alice"));
+        assert_eq!(
+            principal.principal_url,
+            Some(url("/principals/users/alice/"))
+        );
+        assert_eq!(principal.calendar_home_set, vec![url("/calendars/alice/")]);
+        assert!(principal.addressbook_home_set.is_empty());
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "PROPFIND");
+        assert_eq!(requests[0].header("depth"), Some("0"));
+        assert!(String::from_utf8_lossy(&requests[0].body).contains(":current-user-principal/>"));
+        assert_eq!(requests[1].path, "/principals/alice/");
+        let body = String::from_utf8_lossy(&requests[1].body);
+        for name in [
+            "displayname",
+            "principal-URL",
+            "calendar-home-set",
+            "addressbook-home-set",
+        ] {
+            assert!(body.contains(&format!(":{name}/>")), "{name} in {body}");
         }
     }
 
-    fn setstat(&mut self, _path: &Path, _metadata: Metadata) -> RemoteResult<()> {
-        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+    #[test]
+    fn should_not_get_principal_when_unauthenticated_or_unsupported() {
+        crate::mock::logger();
+        let server = MockServer::scripted(vec![
+            MockResponse::new(207).body(prop_response(
+                "/",
+                "<d:current-user-principal><d:unauthenticated/></d:current-user-principal>",
+            )),
+            MockResponse::new(207).body(prop_response("/", "")),
+        ]);
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        assert_eq!(client.current_principal().unwrap(), None);
+        assert_eq!(
+            client.current_principal().unwrap_err().kind,
+            RemoteErrorType::UnsupportedFeature
+        );
     }
 
-    fn exists(&mut self, path: &Path) -> RemoteResult<bool> {
-        debug!("Checking if file exists: {}", path.display());
-        Ok(self.stat(path).is_ok())
+    #[test]
+    fn should_welcome_with_principal_display_name() {
+        crate::mock::logger();
+        let server = principal_server("<d:displayname>Alice</d:displayname>");
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        assert_eq!(client.connect().unwrap().banner.as_deref(), Some("Alice"));
+
+        // servers without principals are still connected to
+        let server = MockServer::new(|_| MockResponse::new(405));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        assert_eq!(client.connect().unwrap().banner, None);
+        assert!(client.is_connected());
     }
 
-    fn remove_file(&mut self, path: &Path) -> RemoteResult<()> {
-        let url = self.url(path, false);
-        debug!("Removing file: {}", url);
-        let response = self
-            .client
-            .delete(&url)
-            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
-
-        ResponseParser::from(response).status()
-    }
+    const ACL_RESPONSE: &str = r#"<?xml version="1.0"?>
+        <d:multistatus xmlns:d="DAV:" xmlns:x="urn:example">
+        <d:response>
+        <d:href>/shared/</d:href>
+        <d:propstat>
+        <d:prop><d:acl>
+        <d:ace>
+        <d:principal><d:href>/principals/users/alice</d:href></d:principal>
+        <d:grant><d:privilege><d:read/></d:privilege><d:privilege><d:write/></d:privilege></d:grant>
+        </d:ace>
+        <d:ace>
+        <d:invert><d:principal><d:property><d:owner/></d:property></d:principal></d:invert>
+        <d:deny><d:privilege><d:write-acl/></d:privilege></d:deny>
+        </d:ace>
+        <d:ace>
+        <d:principal><x:group-members/></d:principal>
+        <d:grant><d:privilege><d:read/></d:privilege></d:grant>
+        </d:ace>
+        <d:ace>
+        <d:principal><d:authenticated/></d:principal>
+        <d:grant><d:privilege><d:read-current-user-privilege-set/></d:privilege></d:grant>
+        <d:protected/>
+        </d:ace>
+        <d:ace>
+        <d:principal><d:all/></d:principal>
+        <d:deny><d:privilege><d:all/></d:privilege></d:deny>
+        <d:inherited><d:href>/</d:href></d:inherited>
+        </d:ace>
+        </d:acl></d:prop>
+        <d:status>HTTP/1.1 200 OK</d:status>
+        </d:propstat>
+        </d:response>
+        </d:multistatus>"#;
 
-    fn remove_dir(&mut self, path: &Path) -> RemoteResult<()> {
-        let url = self.url(path, true);
-        debug!("Removing directory: {}", url);
-        let response = self
-            .client
-            .delete(&url)
-            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+    #[test]
+    fn should_get_acl() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(207).body(ACL_RESPONSE));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let acl = client.get_acl(Path::new("/shared/")).unwrap();
 
-        ResponseParser::from(response).status()
+        assert_eq!(acl.len(), 5);
+        assert_eq!(
+            acl[0],
+            Ace::grant(
+                Principal::Href(Href("/principals/users/alice".parse().unwrap())),
+                vec![Privilege::Read, Privilege::Write]
+            )
+        );
+        assert!(acl[1].invert);
+        assert_eq!(
+            acl[1].principal,
+            Principal::Property(Properties::new().with::<Owner>())
+        );
+        assert_eq!(acl[1].access, Access::Deny(vec![Privilege::WriteAcl]));
+        assert_eq!(
+            acl[2].principal,
+            Principal::Other {
+                namespace: Some("urn:example".into()),
+                name: "group-members".into(),
+                value: webdav_xml::Value::Empty,
+            }
+        );
+        assert_eq!(acl[3].principal, Principal::Authenticated);
+        assert!(acl[3].protected);
+        assert_eq!(acl[4].principal, Principal::All);
+        assert_eq!(acl[4].access.privileges(), &[Privilege::All]);
+        assert_eq!(acl[4].inherited, Some(Href("/".parse().unwrap())));
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PROPFIND");
+        assert_eq!(requests[0].header("depth"), Some("0"));
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains(":acl/>"));
     }
 
-    fn remove_dir_all(&mut self, path: &Path) -> RemoteResult<()> {
-        self.remove_dir(path)
+    #[test]
+    fn should_fail_getting_unreadable_acl() {
+        crate::mock::logger();
+        let server = MockServer::scripted(vec![
+            MockResponse::new(207).body(
+                r#"<?xml version="1.0"?>
+                <d:multistatus xmlns:d="DAV:">
+                <d:response>
+                <d:href>/shared/</d:href>
+                <d:propstat>
+                <d:prop><d:acl/></d:prop>
+                <d:status>HTTP/1.1 403 Forbidden</d:status>
+                </d:propstat>
+                </d:response>
+                </d:multistatus>"#,
+            ),
+            MockResponse::new(207).body(quota_response("1024", "")),
+        ]);
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let err = client.get_acl(Path::new("/shared/")).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::CouldNotOpenFile);
+        // acl not reported at all
+        let err = client.get_acl(Path::new("/shared/")).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::UnsupportedFeature);
     }
 
-    fn create_dir(&mut self, path: &Path, _mode: UnixPex) -> RemoteResult<()> {
-        if self.stat(path).is_ok() {
-            return Err(RemoteError::new(RemoteErrorType::DirectoryAlreadyExists));
-        }
-        let url = self.url(path, true);
-        // check if dir exists
-        debug!("Creating directory: {}", url);
-        let response = self
-            .client
-            .mkcol(&url)
-            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
-
-        ResponseParser::from(response).status()
+    #[test]
+    fn should_set_acl() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(200));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let mut inherited = Ace::deny(Principal::All, vec![Privilege::All]);
+        inherited.inherited = Some(Href("/".parse().unwrap()));
+        let mut inverted = Ace::deny(Principal::SelfPrincipal, vec![Privilege::WriteAcl]);
+        inverted.invert = true;
+        client
+            .set_acl(
+                Path::new("/shared/"),
+                vec![
+                    Ace::grant(
+                        Principal::Href(Href("/principals/users/bob".parse().unwrap())),
+                        vec![Privilege::Read, Privilege::WriteContent],
+                    ),
+                    inverted,
+                    inherited,
+                ],
+            )
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "ACL");
+        assert_eq!(requests[0].path, "/shared/");
+        let body: String = String::from_utf8_lossy(&requests[0].body)
+            .split_whitespace()
+            .collect();
+        assert!(body.contains(
+            "<d:ace><d:principal><d:href>/principals/users/bob</d:href></d:principal><d:grant>\
+             <d:privilege><d:read/></d:privilege><d:privilege><d:write-content/></d:privilege>\
+             </d:grant></d:ace>"
+        ));
+        assert!(body.contains(
+            "<d:ace><d:invert><d:principal><d:self/></d:principal></d:invert><d:deny><d:privilege>\
+             <d:write-acl/></d:privilege></d:deny></d:ace>"
+        ));
+        // inherited entries can't be set
+        assert_eq!(body.matches("<d:ace>").count(), 2);
     }
 
-    fn symlink(&mut self, _path: &Path, _target: &Path) -> RemoteResult<()> {
-        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+    #[test]
+    fn should_map_acl_preconditions() {
+        crate::mock::logger();
+        let server = MockServer::scripted(vec![
+            MockResponse::new(403)
+                .header("Content-Type", "application/xml; charset=utf-8")
+                .body(
+                    r#"<?xml version="1.0"?><d:error xmlns:d="DAV:"><d:no-ace-conflict/></d:error>"#,
+                ),
+            MockResponse::new(403)
+                .header("Content-Type", "application/xml; charset=utf-8")
+                .body(r#"<?xml version="1.0"?><d:error xmlns:d="DAV:"><d:grant-only/></d:error>"#),
+        ]);
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let aces = vec![Ace::deny(Principal::All, vec![Privilege::Write])];
+        let err = client
+            .set_acl(Path::new("/shared/"), aces.clone())
+            .unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::PexError);
+        assert!(err.msg.unwrap().contains("no-ace-conflict"));
+        let err = client.set_acl(Path::new("/shared/"), aces).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::UnsupportedFeature);
     }
 
-    fn copy(&mut self, _src: &Path, _dest: &Path) -> RemoteResult<()> {
-        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+    const LOCK_RESPONSE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+        <D:prop xmlns:D="DAV:">
+        <D:lockdiscovery>
+        <D:activelock>
+        <D:locktype><D:write/></D:locktype>
+        <D:lockscope><D:exclusive/></D:lockscope>
+        <D:depth>infinity</D:depth>
+        <D:owner>alice</D:owner>
+        <D:timeout>Second-600</D:timeout>
+        <D:locktoken><D:href>urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6</D:href></D:locktoken>
+        <D:lockroot><D:href>/docs/</D:href></D:lockroot>
+        </D:activelock>
+        </D:lockdiscovery>
+        </D:prop>"#;
+
+    fn lock_server() -> MockServer {
+        MockServer::new(|request| match request.method.as_str() {
+            "LOCK" => MockResponse::new(200)
+                .header(
+                    "Lock-Token",
+                    "<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>",
+                )
+                .header("Content-Type", "application/xml; charset=utf-8")
+                .body(LOCK_RESPONSE),
+            "UNLOCK" => MockResponse::new(204),
+            _ => MockResponse::new(201),
+        })
     }
 
-    fn mov(&mut self, src: &Path, dest: &Path) -> RemoteResult<()> {
-        let src_url = self.url(src, false);
-        let dest_url = self.url(dest, false);
-        debug!("Moving file: {} to {}", src_url, dest_url);
-
-        let response = self
-            .client
-            .mv(&src_url, &dest_url)
-            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+    #[test]
+    fn should_lock_and_unlock() {
+        crate::mock::logger();
+        let server = lock_server();
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let lock = client
+            .lock(
+                Path::new("/docs/"),
+                LockScope::Exclusive,
+                Depth::Infinity,
+                Timeout::Seconds(3600),
+                Some("alice"),
+            )
+            .unwrap();
+        assert_eq!(lock.path(), Path::new("/docs/"));
+        assert_eq!(
+            lock.token(),
+            "urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6"
+        );
+        assert_eq!(lock.scope(), LockScope::Exclusive);
+        assert_eq!(lock.depth(), Depth::Infinity);
+        // timeout granted by the server
+        assert_eq!(lock.timeout(), Some(Timeout::Seconds(600)));
+        assert_eq!(client.locks().to_vec(), vec![lock.clone()]);
 
-        ResponseParser::from(response).status()
+        let data = "test data\n";
+        client
+            .create_file(
+                Path::new("/docs/a.txt"),
+                &Metadata::default(),
+                Box::new(data.as_bytes()),
+            )
+            .unwrap();
+        client
+            .create_file(
+                Path::new("/other/a.txt"),
+                &Metadata::default(),
+                Box::new(data.as_bytes()),
+            )
+            .unwrap();
+        client.unlock(lock).unwrap();
+        assert!(client.locks().is_empty());
+        client
+            .create_file(
+                Path::new("/docs/a.txt"),
+                &Metadata::default(),
+                Box::new(data.as_bytes()),
+            )
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 5);
+        assert_eq!(requests[0].method, "LOCK");
+        assert_eq!(requests[0].header("depth"), Some("infinity"));
+        assert_eq!(requests[0].header("timeout"), Some("Second-3600"));
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("lockinfo"));
+        assert!(body.contains("exclusive"));
+        assert!(body.contains("alice"));
+        // lock token is submitted only for resources covered by the lock
+        assert_eq!(
+            requests[1].header("if"),
+            Some("(<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>)")
+        );
+        assert_eq!(requests[2].header("if"), None);
+        assert_eq!(requests[3].method, "UNLOCK");
+        assert_eq!(requests[3].path, "/docs/");
+        assert_eq!(
+            requests[3].header("lock-token"),
+            Some("<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>")
+        );
+        assert_eq!(requests[4].header("if"), None);
     }
 
-    fn exec(&mut self, _cmd: &str) -> RemoteResult<(u32, String)> {
-        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
-    }
+    #[test]
+    fn should_submit_lock_token_for_move_destination() {
+        crate::mock::logger();
+        let server = lock_server();
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        client
+            .lock(
+                Path::new("/docs/"),
+                LockScope::Exclusive,
+                Depth::Infinity,
+                Timeout::Infinite,
+                None,
+            )
+            .unwrap();
+        client
+            .mov(Path::new("/inbox/a.txt"), Path::new("/docs/a.txt"))
+            .unwrap();
 
-    fn append(&mut self, _path: &Path, _metadata: &Metadata) -> RemoteResult<WriteStream> {
-        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+        // the source isn't locked, so the list is tagged with the root of the lock on the destination
+        let requests = server.requests();
+        assert_eq!(requests[1].method, "MOVE");
+        assert_eq!(
+            requests[1].header("if"),
+            Some(
+                format!(
+                    "<{}/docs/> (<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>)",
+                    server.url()
+                )
+                .as_str()
+            )
+        );
     }
 
-    fn create(&mut self, _path: &Path, _metadata: &Metadata) -> RemoteResult<WriteStream> {
-        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
-    }
+    #[test]
+    fn should_refresh_lock() {
+        crate::mock::logger();
+        let server = lock_server();
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let mut lock = client
+            .lock(
+                Path::new("/docs/"),
+                LockScope::Exclusive,
+                Depth::Infinity,
+                Timeout::Seconds(60),
+                None,
+            )
+            .unwrap();
+        client
+            .refresh_lock(&mut lock, Timeout::Seconds(3600))
+            .unwrap();
+        assert_eq!(lock.timeout(), Some(Timeout::Seconds(600)));
 
-    fn open(&mut self, _path: &Path) -> RemoteResult<ReadStream> {
-        Err(RemoteError::new(RemoteErrorType::UnsupportedFeature))
+        let requests = server.requests();
+        assert_eq!(requests[1].method, "LOCK");
+        assert_eq!(
+            requests[1].header("if"),
+            Some("(<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>)")
+        );
+        assert_eq!(requests[1].header("timeout"), Some("Second-3600"));
+        assert!(requests[1].body.is_empty());
     }
 
-    fn create_file(
-        &mut self,
-        path: &Path,
-        _metadata: &Metadata,
-        mut reader: Box<dyn std::io::Read + Send>,
-    ) -> RemoteResult<u64> {
-        let url = self.url(path, false);
-        debug!("Creating file: {}", url);
-        let mut content = Vec::new();
-        reader
-            .read_to_end(&mut content)
-            .map_err(|e| RemoteError::new_ex(RemoteErrorType::IoError, e))?;
-        let size = content.len() as u64;
-        let response = self
-            .client
-            .put(content, &url)
-            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
-
-        ResponseParser::from(response).status()?;
-
-        Ok(size)
+    #[test]
+    fn should_fail_lock_on_locked_resource() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| {
+            MockResponse::new(423)
+                .header("Content-Type", "application/xml; charset=utf-8")
+                .body(
+                    r#"<?xml version="1.0" encoding="utf-8"?>
+                    <D:error xmlns:D="DAV:"><D:no-conflicting-lock><D:href>/docs/</D:href></D:no-conflicting-lock></D:error>"#,
+                )
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let err = client
+            .lock(
+                Path::new("/docs/a.txt"),
+                LockScope::Exclusive,
+                Depth::Zero,
+                Timeout::Infinite,
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::PexError);
+        assert!(err.msg.unwrap().contains("no-conflicting-lock"));
+        assert!(client.locks().is_empty());
     }
 
-    fn open_file(
-        &mut self,
-        src: &Path,
-        mut dest: Box<dyn std::io::Write + Send>,
-    ) -> RemoteResult<u64> {
-        let url = self.url(src, false);
-        debug!("Opening file: {}", url);
-        let response = self
-            .client
-            .get(&url)
-            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+    #[test]
+    fn should_lock_protected_operations() {
+        crate::mock::logger();
+        let server = lock_server();
+        let mut client = WebDAVFs::new("user", "password", &server.url()).lock_protected(true);
+        client
+            .create_file(
+                Path::new("/docs/a.txt"),
+                &Metadata::default(),
+                Box::new("test data\n".as_bytes()),
+            )
+            .unwrap();
+        client
+            .mov(Path::new("/docs/a.txt"), Path::new("/docs/b.txt"))
+            .unwrap();
+        client.remove_file(Path::new("/docs/b.txt")).unwrap();
+        assert!(client.locks().is_empty());
 
-        let mut response_parser = ResponseParser::from(response);
-        response_parser.status()?;
+        let requests = server.requests();
+        let methods: Vec<&str> = requests.iter().map(|r| r.method.as_str()).collect();
+        assert_eq!(
+            methods,
+            vec![
+                "LOCK", "PUT", "UNLOCK", "LOCK", "LOCK", "MOVE", "UNLOCK", "UNLOCK", "LOCK",
+                "DELETE", "UNLOCK"
+            ]
+        );
+        assert_eq!(requests[0].path, "/docs/a.txt");
+        assert_eq!(requests[0].header("depth"), Some("0"));
+        assert!(String::from_utf8_lossy(&requests[0].body).contains("exclusive"));
+        for request in [&requests[1], &requests[9]] {
+            assert_eq!(
+                request.header("if"),
+                Some("(<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>)")
+            );
+        }
+        // both the source and the destination of the move are locked
+        assert_eq!(requests[3].path, "/docs/a.txt");
+        assert_eq!(requests[4].path, "/docs/b.txt");
+        assert_eq!(
+            requests[5].header("if"),
+            Some(
+                format!(
+                    "<{url}/docs/a.txt> (<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>) \
+                     <{url}/docs/b.txt> (<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>)",
+                    url = server.url()
+                )
+                .as_str()
+            )
+        );
+        assert_eq!(requests[8].path, "/docs/b.txt");
+    }
 
-        // write to dest
-        let mut buf = vec![0; 1024];
-        let mut total_size = 0;
-        loop {
-            let n = response_parser
-                .read(&mut buf)
-                .map_err(|e| RemoteError::new_ex(RemoteErrorType::IoError, e))?;
-            total_size += n as u64;
-            if n == 0 {
-                return Ok(total_size);
+    #[test]
+    fn should_unlock_after_failed_protected_operation() {
+        crate::mock::logger();
+        // the resource exists when the lock answers 200, and is created empty when it answers 201
+        for (status, expected) in [
+            (200, vec!["LOCK", "PUT", "UNLOCK", "PROPFIND"]),
+            (201, vec!["LOCK", "PUT", "DELETE", "UNLOCK", "PROPFIND"]),
+        ] {
+            let server = MockServer::new(move |request| match request.method.as_str() {
+                "LOCK" => MockResponse::new(status).header(
+                    "Lock-Token",
+                    "<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>",
+                ),
+                "UNLOCK" | "DELETE" => MockResponse::new(204),
+                _ => MockResponse::new(507),
+            });
+            let mut client = WebDAVFs::new("user", "password", &server.url()).lock_protected(true);
+            let err = client
+                .create_file(
+                    Path::new("/docs/a.txt"),
+                    &Metadata::default(),
+                    Box::new("test data\n".as_bytes()),
+                )
+                .unwrap_err();
+            assert_eq!(err.kind, RemoteErrorType::IoError);
+            assert!(client.locks().is_empty());
+            let requests = server.requests();
+            let methods: Vec<&str> = requests.iter().map(|r| r.method.as_str()).collect();
+            // the quota is read after the lock has been released
+            assert_eq!(methods, expected);
+            if status == 201 {
+                assert_eq!(requests[2].path, "/docs/a.txt");
+                assert_eq!(
+                    requests[2].header("if"),
+                    Some("(<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>)")
+                );
             }
-            dest.write_all(&buf[..n])
-                .map_err(|e| RemoteError::new_ex(RemoteErrorType::IoError, e))?;
         }
     }
-}
 
-#[cfg(test)]
-mod test {
-
-    #[cfg(feature = "with-containers")]
-    use std::io::Cursor;
-
-    use pretty_assertions::assert_eq;
-    #[cfg(feature = "with-containers")]
-    use serial_test::serial;
+    #[test]
+    fn should_not_run_protected_operation_on_locked_resource() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(423));
+        let mut client = WebDAVFs::new("user", "password", &server.url()).lock_protected(true);
+        let err = client.remove_file(Path::new("/docs/a.txt")).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::PexError);
+        let methods: Vec<String> = server.requests().into_iter().map(|r| r.method).collect();
+        assert_eq!(methods, vec!["LOCK"]);
+    }
 
-    use super::*;
+    #[test]
+    fn should_not_lock_protected_operation_twice() {
+        crate::mock::logger();
+        let server = lock_server();
+        let mut client = WebDAVFs::new("user", "password", &server.url()).lock_protected(true);
+        let lock = client
+            .lock(
+                Path::new("/docs/"),
+                LockScope::Exclusive,
+                Depth::Infinity,
+                Timeout::Infinite,
+                None,
+            )
+            .unwrap();
+        client.remove_file(Path::new("/docs/a.txt")).unwrap();
+        assert_eq!(client.locks().to_vec(), vec![lock]);
+        let methods: Vec<String> = server.requests().into_iter().map(|r| r.method).collect();
+        assert_eq!(methods, vec!["LOCK", "DELETE"]);
+    }
 
     #[test]
-    fn test_should_init_client() {
+    fn should_map_webdav_status_codes() {
         crate::mock::logger();
-        let client = WebDAVFs::new("user", "password", "http://localhost:3080");
-        assert_eq!(client.url, "http://localhost:3080");
-        assert_eq!(client.wrkdir, "/");
+        for (status, kind) in [
+            (401, RemoteErrorType::AuthenticationFailed),
+            (404, RemoteErrorType::NoSuchFileOrDirectory),
+            (405, RemoteErrorType::UnsupportedFeature),
+            (409, RemoteErrorType::NoSuchFileOrDirectory),
+            (412, RemoteErrorType::ProtocolError),
+            (413, RemoteErrorType::FileCreateDenied),
+            (423, RemoteErrorType::PexError),
+            (424, RemoteErrorType::ProtocolError),
+            (507, RemoteErrorType::IoError),
+        ] {
+            let server = MockServer::new(move |_| MockResponse::new(status));
+            let mut client = WebDAVFs::new("user", "password", &server.url());
+            assert_eq!(
+                client.remove_file(Path::new("/a.txt")).unwrap_err().kind,
+                kind,
+                "status {status}"
+            );
+        }
     }
 
     #[test]
-    fn test_should_get_url() {
-        let mut client = WebDAVFs::new("user", "password", "http://localhost:3080");
-        let path = Path::new("a.txt");
-        assert_eq!(client.url(path, false), "http://localhost:3080/a.txt");
+    fn should_map_mkcol_method_not_allowed_to_already_exists() {
+        crate::mock::logger();
+        let server = MockServer::new(|req| match req.method.as_str() {
+            "MKCOL" => MockResponse::new(405),
+            _ => MockResponse::new(404),
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        assert_eq!(
+            client
+                .create_dir(Path::new("/mydir"), UnixPex::from(0o755))
+                .unwrap_err()
+                .kind,
+            RemoteErrorType::DirectoryAlreadyExists
+        );
+    }
 
-        let path = Path::new("/a.txt");
-        assert_eq!(client.url(path, false), "http://localhost:3080/a.txt");
+    #[test]
+    fn should_attach_request_context_to_error() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| {
+            MockResponse::new(423).body("<p>\n  The resource is   locked\n</p>")
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let err = client.remove_file(Path::new("/a.txt")).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::PexError);
+        assert_eq!(
+            err.msg.unwrap(),
+            format!(
+                "DELETE {}/a.txt returned 423 Locked (resource is locked): <p> The resource is locked </p>",
+                server.url()
+            )
+        );
+    }
 
-        let path = Path::new("/");
-        assert_eq!(client.url(path, false), "http://localhost:3080/");
+    #[test]
+    fn should_report_error_precondition() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| {
+            MockResponse::new(423)
+                .header("Content-Type", "application/xml; charset=utf-8")
+                .body(
+                    r#"<?xml version="1.0" encoding="utf-8"?>
+                    <D:error xmlns:D="DAV:">
+                    <D:lock-token-submitted><D:href>/a.txt</D:href></D:lock-token-submitted>
+                    </D:error>"#,
+                )
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let err = client.remove_file(Path::new("/a.txt")).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::PexError);
+        assert_eq!(
+            err.msg.unwrap(),
+            format!(
+                "DELETE {}/a.txt returned 423 Locked (resource is locked): lock-token-submitted (/a.txt)",
+                server.url()
+            )
+        );
+    }
 
-        client.wrkdir = "/test/".to_string();
-        let path = Path::new("a.txt");
-        assert_eq!(client.url(path, false), "http://localhost:3080/test/a.txt");
+    #[test]
+    fn should_report_connection_error() {
+        crate::mock::logger();
+        let url = {
+            let server = MockServer::new(|_| MockResponse::new(200));
+            server.url()
+        };
+        let mut client = WebDAVFs::new("user", "password", &url);
+        let err = client.stat(Path::new("/a.txt")).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::ConnectionError);
+        assert!(err.msg.unwrap().starts_with("PROPFIND "));
+    }
 
-        let path = Path::new("/a.txt");
-        assert_eq!(client.url(path, false), "http://localhost:3080/a.txt");
+    #[test]
+    fn should_not_parse_error_from_html_body() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| {
+            MockResponse::new(500)
+                .header("Content-Type", "text/html; charset=utf-8")
+                .body("<html><script><![CDATA[alert(1)]]></script><body>Oops</body></html>")
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let err = client.stat(Path::new("/a.txt")).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::ProtocolError);
+        assert_eq!(
+            err.msg.unwrap(),
+            format!(
+                "PROPFIND {}/a.txt returned 500 Internal Server Error: <html><script><![CDATA[alert(1)]]></script><body>Oops</body></html>",
+                server.url()
+            )
+        );
+    }
 
-        let path = Path::new("/gabibbo");
-        assert_eq!(client.url(path, true), "http://localhost:3080/gabibbo/");
+    #[test]
+    fn should_parse_cdata_in_error_body() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| {
+            MockResponse::new(409)
+                .header("Content-Type", "application/xml; charset=utf-8")
+                .body(
+                    r#"<?xml version="1.0" encoding="utf-8"?>
+                    <d:error xmlns:d="DAV:" xmlns:s="http://sabredav.org/ns">
+                    <s:message><![CDATA[Parent node <docs> does not exist]]></s:message>
+                    </d:error>"#,
+                )
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let err = client
+            .create_dir(Path::new("/docs/a/"), UnixPex::from(0o755))
+            .unwrap_err();
+        assert!(err
+            .msg
+            .unwrap()
+            .ends_with(": Parent node <docs> does not exist"));
     }
 
     #[test]
@@ -496,7 +3471,8 @@ mod test {
     #[cfg(feature = "with-containers")]
     fn should_list_dir() {
         crate::mock::logger();
-        let mut client = setup_client();
+        let mut client = setup_client()
+            .listing_properties(Properties::new().with::<webdav_xml::properties::Executable>());
         // Create file
         let wrkdir = client.pwd().ok().unwrap();
         let p = Path::new("a.txt");
@@ -519,7 +3495,7 @@ mod test {
         assert_eq!(file.path.as_path(), expected_path.as_path());
         assert_eq!(file.extension().as_deref().unwrap(), "txt");
         assert_eq!(file.metadata.size, 10);
-        assert_eq!(file.metadata.mode, None);
+        assert_eq!(file.metadata.mode, Some(UnixPex::from(0o644)));
         finalize_client(client);
     }
 
@@ -0,0 +1,265 @@
+//! ## Lock
+//!
+//! Locks held on remote resources
+
+use std::path::{Path, PathBuf};
+
+use http::Method;
+use reqwest::Url;
+
+use crate::webdav_xml::elements::{Depth, LockScope, Timeout};
+
+/// Methods which modify a resource and so must submit the tokens of the locks held on it
+const MUTATING_METHODS: &[&str] = &[
+    "PUT",
+    "POST",
+    "DELETE",
+    "MKCOL",
+    "MOVE",
+    "COPY",
+    "PROPPATCH",
+    "ACL",
+];
+
+/// A write lock held on a remote resource, as returned by [`crate::WebDAVFs::lock`].
+///
+/// While the lock is held, its token is submitted in the `If` header of every request modifying a resource
+/// covered by the lock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lock {
+    path: PathBuf,
+    url: Url,
+    token: String,
+    scope: LockScope,
+    depth: Depth,
+    timeout: Option<Timeout>,
+    created: bool,
+}
+
+impl Lock {
+    pub(crate) fn new(
+        path: PathBuf,
+        url: Url,
+        token: String,
+        scope: LockScope,
+        depth: Depth,
+        timeout: Option<Timeout>,
+        created: bool,
+    ) -> Self {
+        Self {
+            path,
+            url,
+            token,
+            scope,
+            depth,
+            timeout,
+            created,
+        }
+    }
+
+    /// Path of the locked resource
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Url of the locked resource
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// The lock token, as returned by the server in the `Lock-Token` header
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Scope of the lock
+    pub fn scope(&self) -> LockScope {
+        self.scope
+    }
+
+    /// Depth of the lock
+    pub fn depth(&self) -> Depth {
+        self.depth
+    }
+
+    /// Timeout of the lock granted by the server, if reported
+    pub fn timeout(&self) -> Option<Timeout> {
+        self.timeout
+    }
+
+    /// Returns whether locking created an empty resource, since nothing was mapped to the url
+    /// (`201 Created`)
+    pub fn created(&self) -> bool {
+        self.created
+    }
+
+    pub(crate) fn set_timeout(&mut self, timeout: Option<Timeout>) {
+        self.timeout = timeout;
+    }
+
+    /// Returns whether the lock covers the resource at `url`, i.e. `url` is the locked resource or, for
+    /// a lock with infinite depth, one of its members.
+    pub(crate) fn covers(&self, url: &Url) -> bool {
+        if url.origin() != self.url.origin() {
+            return false;
+        }
+        let root = self.url.path().trim_end_matches('/');
+        let path = url.path().trim_end_matches('/');
+        path == root
+            || (self.depth == Depth::Infinity
+                && path
+                    .strip_prefix(root)
+                    .is_some_and(|member| member.starts_with('/')))
+    }
+}
+
+/// Returns whether requests with `method` modify a resource
+pub(crate) fn is_mutating(method: &Method) -> bool {
+    MUTATING_METHODS.contains(&method.as_str())
+}
+
+/// Build the value of the `If` header submitting the tokens of `locks` in a request to `url`.
+///
+/// Untagged lists apply to the request url, so they are used only if every lock covers it, e.g.
+/// `(<token1>) (<token2>)`. Otherwise, e.g. when only the destination of a `MOVE` is locked, each list is
+/// tagged with the root of its lock, e.g. `<http://host/docs/> (<token>)`, since both kinds of lists can't be
+/// mixed.
+pub(crate) fn if_header<'a>(
+    url: &Url,
+    locks: impl IntoIterator<Item = &'a Lock>,
+) -> Option<String> {
+    let locks: Vec<&Lock> = locks.into_iter().collect();
+    if locks.is_empty() {
+        return None;
+    }
+    let tagged = locks.iter().any(|lock| !lock.covers(url));
+    let lists: Vec<String> = locks
+        .into_iter()
+        .map(|lock| {
+            if tagged {
+                format!("<{}> (<{}>)", lock.url, lock.token)
+            } else {
+                format!("(<{}>)", lock.token)
+            }
+        })
+        .collect();
+
+    Some(lists.join(" "))
+}
+
+/// Parse the value of the `Lock-Token` header, which is a coded URL, e.g. `<urn:uuid:...>`
+pub(crate) fn parse_lock_token(value: &str) -> Option<String> {
+    let token = value.trim();
+    let token = token
+        .strip_prefix('<')
+        .and_then(|token| token.strip_suffix('>'))
+        .unwrap_or(token)
+        .trim();
+    if token.is_empty() {
+        None
+    } else {
+        Some(token.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::client::method;
+
+    fn lock(url: &str, depth: Depth) -> Lock {
+        Lock::new(
+            PathBuf::from("/docs"),
+            Url::parse(url).unwrap(),
+            String::from("urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6"),
+            LockScope::Exclusive,
+            depth,
+            None,
+            false,
+        )
+    }
+
+    #[test]
+    fn should_tell_whether_lock_covers_url() {
+        let url = |url: &str| Url::parse(url).unwrap();
+
+        let file = lock("http://localhost/docs/report.odt", Depth::Zero);
+        assert!(file.covers(&url("http://localhost/docs/report.odt")));
+        assert!(!file.covers(&url("http://localhost/docs/report.odt.bak")));
+        assert!(!file.covers(&url("http://localhost:8080/docs/report.odt")));
+
+        let dir = lock("http://localhost/docs/", Depth::Infinity);
+        assert!(dir.covers(&url("http://localhost/docs")));
+        assert!(dir.covers(&url("http://localhost/docs/report.odt")));
+        assert!(dir.covers(&url("http://localhost/docs/2024/report.odt")));
+        assert!(!dir.covers(&url("http://localhost/docs-old/report.odt")));
+        assert!(!dir.covers(&url("http://localhost/")));
+
+        let dir = lock("http://localhost/docs/", Depth::Zero);
+        assert!(dir.covers(&url("http://localhost/docs/")));
+        assert!(!dir.covers(&url("http://localhost/docs/report.odt")));
+    }
+
+    #[test]
+    fn should_tell_whether_method_is_mutating() {
+        assert!(is_mutating(&Method::PUT));
+        assert!(is_mutating(&Method::DELETE));
+        assert!(is_mutating(&method("MOVE")));
+        assert!(is_mutating(&method("PROPPATCH")));
+        assert!(!is_mutating(&Method::GET));
+        assert!(!is_mutating(&method("PROPFIND")));
+        assert!(!is_mutating(&method("LOCK")));
+    }
+
+    #[test]
+    fn should_build_if_header() {
+        let url = Url::parse("http://localhost/docs/report.odt").unwrap();
+        let file = lock("http://localhost/docs/report.odt", Depth::Zero);
+        assert_eq!(if_header(&url, []), None);
+        assert_eq!(
+            if_header(&url, [&file]).as_deref(),
+            Some("(<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>)")
+        );
+        assert_eq!(
+            if_header(&url, [&file, &file]).as_deref(),
+            Some(
+                "(<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>) \
+                 (<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>)"
+            )
+        );
+    }
+
+    #[test]
+    fn should_tag_if_header_with_lock_root_not_covering_url() {
+        let url = Url::parse("http://localhost/inbox/report.odt").unwrap();
+        let file = lock("http://localhost/inbox/report.odt", Depth::Zero);
+        let dir = lock("http://localhost/docs/", Depth::Infinity);
+        assert_eq!(
+            if_header(&url, [&dir]).as_deref(),
+            Some("<http://localhost/docs/> (<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>)")
+        );
+        assert_eq!(
+            if_header(&url, [&file, &dir]).as_deref(),
+            Some(
+                "<http://localhost/inbox/report.odt> (<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>) \
+                 <http://localhost/docs/> (<urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6>)"
+            )
+        );
+    }
+
+    #[test]
+    fn should_parse_lock_token_header() {
+        assert_eq!(
+            parse_lock_token(" <opaquelocktoken:e71d4fae-5dec> ").as_deref(),
+            Some("opaquelocktoken:e71d4fae-5dec")
+        );
+        assert_eq!(
+            parse_lock_token("urn:uuid:e71d4fae").as_deref(),
+            Some("urn:uuid:e71d4fae")
+        );
+        assert_eq!(parse_lock_token("<>"), None);
+    }
+}
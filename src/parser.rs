@@ -1,43 +1,515 @@
+use std::fmt;
 use std::io;
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read};
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use remotefs::fs::{FileType, Metadata};
+use http::Method;
+use nonempty::NonEmpty;
+use remotefs::fs::{FileType, Metadata, UnixPex, UnixPexClass};
 use remotefs::{File, RemoteError, RemoteErrorType, RemoteResult};
-use rustydav::prelude::Response;
+use reqwest::blocking::Response;
+use reqwest::header::{CONTENT_TYPE, LOCATION};
+use reqwest::Url;
 
-use super::webdav_xml::elements::{Multistatus, Response as WebDAVResponse};
-use super::webdav_xml::FromXml;
+use super::client::method;
+use super::principal::CurrentPrincipal;
+use super::quota::Quota;
+use super::sync::{SyncChanges, SyncResult};
+use super::version::Version;
+use super::webdav_xml::elements::{
+    Ace, ActiveLock, Condition, Error as ErrorElement, Href, LockEntry, Multistatus, Privilege,
+    Properties, Propstat, Response as WebDAVResponse, ResponseDescription, Status,
+};
+use super::webdav_xml::properties::{
+    AddressbookHomeSet, CalendarHomeSet, CreatorDisplayName, CurrentUserPrincipal, DisplayName,
+    PrincipalUrl, QuotaAvailableBytes, QuotaUsedBytes, VersionName,
+};
+use super::webdav_xml::{Error as XmlError, FromXml, ResponseReader};
 
-pub struct ResponseParser {
-    response: Response,
+/// Maximum length of the response body reported in errors
+const ERROR_BODY_MAX_LEN: usize = 512;
+/// Mode of files reported as executable by the server
+const EXECUTABLE_FILE_MODE: u32 = 0o755;
+/// Mode of files reported as not executable by the server
+const FILE_MODE: u32 = 0o644;
+
+/// Entries of a multistatus listing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Listing {
+    /// Entries returned by the server
+    pub entries: Vec<Entry>,
+    /// Entries which the server reported with an unsuccessful status, e.g. unreadable children
+    pub errors: Vec<EntryError>,
+}
+
+/// An entry of a multistatus listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The file described by the entry
+    pub file: File,
+    /// Principal owning the entry, from the `owner` property
+    pub owner: Option<String>,
+    /// Principal group of the entry, from the `group` property
+    pub group: Option<String>,
+    /// Locks currently held on the entry, from the `lockdiscovery` property
+    pub locks: Vec<ActiveLock>,
+    /// Kinds of locks supported by the entry, from the `supportedlock` property
+    pub supported_locks: Vec<LockEntry>,
+    /// Privileges of the current user on the entry, from the `current-user-privilege-set` property
+    pub privileges: Option<Vec<Privilege>>,
+}
+
+impl Entry {
+    /// Entry of the directory at `path`, without any other property
+    #[cfg(feature = "find")]
+    pub(crate) fn directory(path: PathBuf) -> Self {
+        Self {
+            file: File {
+                path,
+                metadata: Metadata {
+                    file_type: FileType::Directory,
+                    ..Metadata::default()
+                },
+            },
+            owner: None,
+            group: None,
+            locks: Vec::new(),
+            supported_locks: Vec::new(),
+            privileges: None,
+        }
+    }
+}
+
+/// An entry of a multistatus listing which the server reported with an unsuccessful status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryError {
+    /// Path of the entry
+    pub path: PathBuf,
+    /// Error reported for the entry
+    pub error: RemoteError,
+}
+
+/// Outcome of the update of a single property in a `PROPPATCH` request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyResult {
+    /// Namespace of the property, if any
+    pub namespace: Option<String>,
+    /// Local name of the property
+    pub name: String,
+    /// Error reported by the server if the property could not be updated
+    pub result: RemoteResult<()>,
 }
 
-impl From<Response> for ResponseParser {
-    fn from(response: Response) -> Self {
-        ResponseParser { response }
+/// Map the privileges of the current user on an entry of type `file_type` to the user bits of its mode.
+///
+/// `read` grants reading and, on directories, traversing; `write` and `write-content` grant writing files,
+/// while directories are writable with `write` or with both `bind` and `unbind`. `all` grants everything.
+/// The execute bit of files and the group and others bits are taken from `mode` (i.e. from the `executable`
+/// property) when known, otherwise they are unset.
+fn privileges_mode(
+    privileges: &[Privilege],
+    file_type: &FileType,
+    mode: Option<UnixPex>,
+) -> UnixPex {
+    let has = |privilege: Privilege| {
+        privileges
+            .iter()
+            .any(|granted| *granted == privilege || *granted == Privilege::All)
+    };
+    let read = has(Privilege::Read);
+    let (write, execute) = match file_type {
+        FileType::Directory => (
+            has(Privilege::Write) || (has(Privilege::Bind) && has(Privilege::Unbind)),
+            read,
+        ),
+        _ => (
+            has(Privilege::Write) || has(Privilege::WriteContent),
+            mode.map(|mode| mode.user().execute()).unwrap_or_default(),
+        ),
+    };
+    let (group, others) = match mode {
+        Some(mode) => (mode.group(), mode.others()),
+        None => (UnixPexClass::from(0), UnixPexClass::from(0)),
+    };
+
+    UnixPex::new(UnixPexClass::new(read, write, execute), group, others)
+}
+
+/// Resolve `href` against `url`
+fn resolve(url: &Url, href: &Href) -> RemoteResult<Url> {
+    url.join(&href.0.to_string()).map_err(|err| {
+        RemoteError::new_ex(
+            RemoteErrorType::ProtocolError,
+            format!("invalid href {}: {err}", href.0),
+        )
+    })
+}
+
+/// Resolve each of `hrefs` against `url`, skipping the invalid ones
+fn resolve_all(url: &Url, hrefs: &[Href]) -> Vec<Url> {
+    hrefs
+        .iter()
+        .filter_map(|href| match resolve(url, href) {
+            Ok(url) => Some(url),
+            Err(err) => {
+                warn!("{err}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Function mapping an owner or group principal to a unix id
+pub(crate) type PrincipalIds = Arc<dyn Fn(&str) -> Option<u32> + Send + Sync>;
+
+/// Lazy iterator over the entries of a multistatus listing, parsing each entry as soon as it has been received.
+///
+/// Entries which the server reported with an unsuccessful status, or whose `response` element cannot be parsed,
+/// are not yielded but collected in [`Entries::errors`], unless the listing is strict. The iteration ends after
+/// the first other error, e.g. a malformed body; dropping the iterator before the end closes the connection.
+pub struct Entries {
+    responses: ResponseReader<Box<dyn BufRead + Send>>,
+    errors: Vec<EntryError>,
+    principal_ids: Option<PrincipalIds>,
+    strict: bool,
+    done: bool,
+}
+
+impl Entries {
+    fn new(reader: impl BufRead + Send + 'static) -> Self {
+        Self {
+            responses: ResponseReader::new(Box::new(reader)),
+            errors: Vec::new(),
+            principal_ids: None,
+            strict: false,
+            done: false,
+        }
     }
+
+    /// Set whether entries reported with an unsuccessful status must be yielded as an error, ending the iteration
+    pub(crate) fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Set the function mapping owner and group principals to the `uid` and `gid` of the entries
+    pub(crate) fn principal_ids(mut self, map: Option<PrincipalIds>) -> Self {
+        self.principal_ids = map;
+        self
+    }
+
+    /// Entries reported with an unsuccessful status so far
+    pub fn errors(&self) -> &[EntryError] {
+        &self.errors
+    }
+
+    /// Collect the remaining entries into a [`Listing`]
+    pub fn into_listing(mut self) -> RemoteResult<Listing> {
+        let entries = self.by_ref().collect::<RemoteResult<Vec<_>>>()?;
+
+        Ok(Listing {
+            entries,
+            errors: self.errors,
+        })
+    }
+}
+
+impl Iterator for Entries {
+    type Item = RemoteResult<Entry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let response = match self.responses.next()? {
+                Ok(response) => response,
+                Err(XmlError::InvalidResponse { href, source }) => {
+                    warn!("invalid response for {href:?}: {source}");
+                    let error = EntryError {
+                        path: PathBuf::from(href.unwrap_or_default()),
+                        error: RemoteError::new_ex(RemoteErrorType::ProtocolError, source),
+                    };
+                    self.errors.push(error.clone());
+                    if self.strict {
+                        self.done = true;
+                        return Some(Err(error.error));
+                    }
+                    continue;
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(RemoteError::new_ex(
+                        RemoteErrorType::ProtocolError,
+                        err,
+                    )));
+                }
+            };
+            trace!("parsed response: {:?}", response);
+            match ResponseParser::entry(response) {
+                Ok(mut entry) => {
+                    if let Some(map) = &self.principal_ids {
+                        set_principal_ids(&mut entry, map);
+                    }
+                    return Some(Ok(entry));
+                }
+                Err(mut errors) => {
+                    let first = errors.first().map(|entry| entry.error.clone());
+                    self.errors.append(&mut errors);
+                    if let (true, Some(error)) = (self.strict, first) {
+                        self.done = true;
+                        return Some(Err(error));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Set the `uid` and `gid` of `entry` from its owner and group principals
+fn set_principal_ids(entry: &mut Entry, map: &PrincipalIds) {
+    entry.file.metadata.uid = entry.owner.as_deref().and_then(|x| map(x));
+    entry.file.metadata.gid = entry.group.as_deref().and_then(|x| map(x));
+}
+
+pub struct ResponseParser {
+    method: Method,
+    response: Response,
 }
 
 impl ResponseParser {
-    pub fn status(&self) -> RemoteResult<()> {
+    pub fn new(method: Method, response: Response) -> Self {
+        ResponseParser { method, response }
+    }
+
+    /// Url of the resource which answered the request
+    pub fn url(&self) -> &Url {
+        self.response.url()
+    }
+
+    /// Value of the response header `name`, if any
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.response.headers().get(name)?.to_str().ok()
+    }
+
+    /// Status code of the response
+    pub fn status_code(&self) -> u16 {
+        self.response.status().as_u16()
+    }
+
+    pub fn status(&mut self) -> RemoteResult<()> {
         if self.response.status().is_success() {
             Ok(())
         } else {
-            match self.response.status().as_u16() {
-                401 => Err(RemoteError::new(RemoteErrorType::AuthenticationFailed)),
-                403 => Err(RemoteError::new(RemoteErrorType::CouldNotOpenFile)),
-                400 | 404 => Err(RemoteError::new(RemoteErrorType::NoSuchFileOrDirectory)),
-                _ => Err(RemoteError::new(RemoteErrorType::ProtocolError)),
+            Err(self.error())
+        }
+    }
+
+    /// Build the error for an unsuccessful response, consuming its body
+    fn error(&mut self) -> RemoteError {
+        self.failure().0
+    }
+
+    /// Build the error for an unsuccessful response as [`ResponseParser::error`] does, along with the `error`
+    /// element of its body, if any
+    fn failure(&mut self) -> (RemoteError, Option<ErrorElement>) {
+        let status = self.response.status().as_u16();
+        let (mut kind, reason) = status_error_kind(status, &self.method);
+        let location = self
+            .response
+            .headers()
+            .get(LOCATION)
+            .and_then(|x| x.to_str().ok())
+            .map(str::to_string);
+
+        let xml = self.has_xml_body();
+        let body = self.error_body();
+        let condition = body.as_ref().filter(|_| xml).and_then(Self::parse_error);
+        if let Some(condition_kind) = condition.as_ref().and_then(Self::condition_kind) {
+            kind = condition_kind;
+        }
+        let error = StatusError {
+            method: self.method.clone(),
+            url: self.response.url().to_string(),
+            status: http::StatusCode::from_u16(status).expect("status is valid"),
+            reason,
+            location,
+            body: match &condition {
+                Some(condition) => Some(condition.to_string()),
+                None => body.as_deref().and_then(Self::error_text),
+            },
+        };
+        debug!("request failed: {error}");
+
+        (RemoteError::new_ex(kind, error), condition)
+    }
+
+    /// Returns whether the content type of the response is XML; other bodies, such as html error pages, are
+    /// never parsed as an `error` element.
+    fn has_xml_body(&self) -> bool {
+        self.response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.split(';').next())
+            .map(|mime| mime.trim().to_ascii_lowercase())
+            .is_some_and(|mime| {
+                mime == "application/xml" || mime == "text/xml" || mime.ends_with("+xml")
+            })
+    }
+
+    /// Read the response body of an unsuccessful response
+    fn error_body(&mut self) -> Option<bytes::Bytes> {
+        let mut body = Vec::new();
+        if let Err(err) = self.response.read_to_end(&mut body) {
+            debug!("failed to read error body: {err}");
+            return None;
+        }
+
+        Some(body.into())
+    }
+
+    /// Parse the `error` element in the body of an unsuccessful response, if any
+    fn parse_error(body: &bytes::Bytes) -> Option<ErrorElement> {
+        let error = ErrorElement::from_xml(body.clone()).ok()?;
+        if error.conditions.is_empty() && error.exception.is_none() && error.message.is_none() {
+            return None;
+        }
+        debug!("parsed error element: {:?}", error);
+
+        Some(error)
+    }
+
+    /// Get the error kind which better describes the condition in `error`
+    fn condition_kind(error: &ErrorElement) -> Option<RemoteErrorType> {
+        error
+            .conditions
+            .iter()
+            .find_map(|condition| match condition {
+                Condition::LockTokenMatchesRequestUri
+                | Condition::LockTokenSubmitted(_)
+                | Condition::NoConflictingLock(_)
+                | Condition::CannotModifyProtectedProperty => Some(RemoteErrorType::PexError),
+                Condition::PropfindFiniteDepth
+                | Condition::SupportedReport
+                | Condition::LimitedNumberOfAces
+                | Condition::GrantOnly
+                | Condition::NoInvert
+                | Condition::NoAbstract
+                | Condition::NotSupportedPrivilege => Some(RemoteErrorType::UnsupportedFeature),
+                Condition::NoAceConflict
+                | Condition::NoProtectedAceConflict
+                | Condition::NoInheritedAceConflict
+                | Condition::DenyBeforeGrant
+                | Condition::MissingRequiredPrincipal
+                | Condition::AllowedPrincipal => Some(RemoteErrorType::PexError),
+                Condition::RecognizedPrincipal => Some(RemoteErrorType::NoSuchFileOrDirectory),
+                _ => None,
+            })
+    }
+
+    /// Format the body of an unsuccessful response as text
+    fn error_text(body: &[u8]) -> Option<String> {
+        let body = String::from_utf8_lossy(body);
+        let mut body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+        if body.is_empty() {
+            return None;
+        }
+        if body.len() > ERROR_BODY_MAX_LEN {
+            let mut end = ERROR_BODY_MAX_LEN;
+            while !body.is_char_boundary(end) {
+                end -= 1;
             }
+            body.truncate(end);
+            body.push_str("...");
         }
+
+        Some(body)
     }
 
-    pub fn files(self) -> RemoteResult<Vec<File>> {
-        debug!("Parsing files from response");
+    /// Parse the entries of a multistatus listing lazily, as the body of a successful response is received
+    pub fn entries(mut self) -> RemoteResult<Entries> {
         if !self.response.status().is_success() {
             debug!("response is not success, returning status");
-            return Err(self.status().unwrap_err());
+            return Err(self.error());
+        }
+        debug!("Parsing entries from response");
+
+        Ok(Entries::new(BufReader::new(self.response)))
+    }
+
+    /// Parse the changes reported by a `sync-collection` report as the body of a successful response is received.
+    ///
+    /// A sync token which the server doesn't know anymore (`valid-sync-token` precondition) is reported as
+    /// [`SyncResult::ResyncRequired`].
+    pub fn sync(mut self, principal_ids: Option<PrincipalIds>) -> RemoteResult<SyncResult> {
+        if !self.response.status().is_success() {
+            let (error, condition) = self.failure();
+            if condition.is_some_and(|error| error.conditions.contains(&Condition::ValidSyncToken))
+            {
+                debug!("sync token is not valid anymore: {error}");
+                return Ok(SyncResult::ResyncRequired);
+            }
+            return Err(error);
+        }
+        debug!("Parsing sync changes from response");
+
+        Self::sync_changes(BufReader::new(self.response), principal_ids).map(SyncResult::Changes)
+    }
+
+    /// Parse the changes of the multistatus of a `sync-collection` report read from `reader`
+    fn sync_changes(
+        reader: impl BufRead,
+        principal_ids: Option<PrincipalIds>,
+    ) -> RemoteResult<SyncChanges> {
+        let mut responses = ResponseReader::new(reader);
+        let mut changes = SyncChanges::default();
+        for response in responses.by_ref() {
+            let response =
+                response.map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+            trace!("parsed response: {:?}", response);
+            match response {
+                WebDAVResponse::Status { href, status, .. }
+                    if status.0 == http::StatusCode::NOT_FOUND =>
+                {
+                    changes.removed.extend(
+                        href.into_iter()
+                            .map(|href| PathBuf::from(href.0.to_string())),
+                    );
+                }
+                // the collection itself, when the changes have been truncated
+                WebDAVResponse::Status { status, .. }
+                    if status.0 == http::StatusCode::INSUFFICIENT_STORAGE =>
+                {
+                    debug!("sync changes have been truncated");
+                    changes.truncated = true;
+                }
+                response => match Self::entry(response) {
+                    Ok(mut entry) => {
+                        if let Some(map) = &principal_ids {
+                            set_principal_ids(&mut entry, map);
+                        }
+                        changes.entries.push(entry);
+                    }
+                    Err(mut errors) => changes.errors.append(&mut errors),
+                },
+            }
+        }
+        changes.token = responses
+            .sync_token()
+            .map(|token| token.0.to_string())
+            .ok_or_else(|| {
+                RemoteError::new_ex(RemoteErrorType::ProtocolError, "missing sync-token")
+            })?;
+
+        Ok(changes)
+    }
+
+    /// Parse the `multistatus` element in the body of a successful response
+    pub fn multistatus(mut self) -> RemoteResult<Multistatus> {
+        if !self.response.status().is_success() {
+            debug!("response is not success, returning status");
+            return Err(self.error());
         }
 
         // parse body
@@ -45,81 +517,586 @@ impl ResponseParser {
             .response
             .bytes()
             .map_err(|e| RemoteError::new_ex(RemoteErrorType::IoError, e))?;
-        trace!(
-            "parsing body: {}",
-            String::from_utf8(bytes.to_vec()).unwrap()
-        );
+        trace!("parsing body: {}", String::from_utf8_lossy(&bytes));
 
-        Self::parse_propfind(bytes)
+        Self::parse_multistatus(bytes)
     }
 
-    pub fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.response.read(buf)
+    /// Parse the locks of the `lockdiscovery` property returned in the body of a successful `LOCK` response.
+    ///
+    /// An unparsable body is reported as an empty list, since the lock token is also sent in the `Lock-Token`
+    /// header.
+    pub fn locks(mut self) -> RemoteResult<Vec<ActiveLock>> {
+        self.status()?;
+        let bytes = self
+            .response
+            .bytes()
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::IoError, e))?;
+
+        match Properties::from_xml(bytes).map(|prop| prop.lockdiscovery()) {
+            Ok(Some(Some(Ok(lockdiscovery)))) => Ok(lockdiscovery.0),
+            Ok(_) => Ok(Vec::new()),
+            Err(err) => {
+                debug!("could not parse lock response: {err}");
+                Ok(Vec::new())
+            }
+        }
     }
 
-    fn parse_propfind(bytes: impl Into<bytes::Bytes>) -> RemoteResult<Vec<File>> {
-        let multistatus = Multistatus::from_xml(bytes)
-            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
-        debug!("parsed multistatus: {:?}", multistatus);
+    /// Parse the quota properties of the first resource of a multistatus response.
+    ///
+    /// Properties which are missing, reported with an error status or invalid are unknown.
+    pub fn quota(self) -> RemoteResult<Quota> {
+        let props = self.properties()?;
+        let mut quota = Quota::default();
+        match props.get::<QuotaAvailableBytes>() {
+            Some(Some(Ok(available))) => quota.available = available.0,
+            Some(Some(Err(err))) => warn!("invalid quota-available-bytes: {err}"),
+            _ => {}
+        }
+        match props.get::<QuotaUsedBytes>() {
+            Some(Some(Ok(used))) => quota.used = used.0,
+            Some(Some(Err(err))) => warn!("invalid quota-used-bytes: {err}"),
+            _ => {}
+        }
 
-        let mut files = Vec::new();
+        Ok(quota)
+    }
 
-        // collect files
-        for response in multistatus.response {
-            let (path, propstats) = match response {
-                WebDAVResponse::Propstat {
-                    href: path,
-                    propstat,
-                    responsedescription: _,
-                } => (path, propstat),
-                _ => {
-                    debug!("Skipping response, not propstat");
+    /// Parse the `current-user-principal` property of the first resource of a multistatus response, resolving
+    /// the principal href against the request url.
+    ///
+    /// Returns `None` if the request has not been authenticated; fails with
+    /// [`RemoteErrorType::UnsupportedFeature`] if the server doesn't report the property.
+    pub fn current_user_principal(self) -> RemoteResult<Option<Url>> {
+        let url = self.url().clone();
+        match self.properties()?.get::<CurrentUserPrincipal>() {
+            Some(Some(Ok(CurrentUserPrincipal::Href(href)))) => resolve(&url, &href).map(Some),
+            Some(Some(Ok(CurrentUserPrincipal::Unauthenticated))) => Ok(None),
+            Some(Some(Err(err))) => Err(RemoteError::new_ex(
+                RemoteErrorType::ProtocolError,
+                format!("invalid current-user-principal: {err}"),
+            )),
+            _ => Err(RemoteError::new_ex(
+                RemoteErrorType::UnsupportedFeature,
+                "current-user-principal property not reported",
+            )),
+        }
+    }
+
+    /// Parse the properties of the principal resource at `url` from the first resource of a multistatus
+    /// response.
+    ///
+    /// Properties which are missing, reported with an error status or invalid are unknown.
+    pub fn principal(self, url: Url) -> RemoteResult<CurrentPrincipal> {
+        let props = self.properties()?;
+        let mut principal = CurrentPrincipal {
+            url,
+            display_name: None,
+            principal_url: None,
+            calendar_home_set: Vec::new(),
+            addressbook_home_set: Vec::new(),
+        };
+        match props.get::<DisplayName>() {
+            Some(Some(Ok(name))) => principal.display_name = Some(name.0.to_string()),
+            Some(Some(Err(err))) => warn!("invalid displayname: {err}"),
+            _ => {}
+        }
+        match props.get::<PrincipalUrl>() {
+            Some(Some(Ok(href))) => principal.principal_url = resolve(&principal.url, &href.0).ok(),
+            Some(Some(Err(err))) => warn!("invalid principal-URL: {err}"),
+            _ => {}
+        }
+        match props.get::<CalendarHomeSet>() {
+            Some(Some(Ok(set))) => {
+                principal.calendar_home_set = resolve_all(&principal.url, &set.0);
+            }
+            Some(Some(Err(err))) => warn!("invalid calendar-home-set: {err}"),
+            _ => {}
+        }
+        match props.get::<AddressbookHomeSet>() {
+            Some(Some(Ok(set))) => {
+                principal.addressbook_home_set = resolve_all(&principal.url, &set.0);
+            }
+            Some(Some(Err(err))) => warn!("invalid addressbook-home-set: {err}"),
+            _ => {}
+        }
+
+        Ok(principal)
+    }
+
+    /// Parse the versions reported by a `version-tree` report, resolving their href against the request url.
+    ///
+    /// Versions reported with an error status are skipped.
+    pub fn versions(self) -> RemoteResult<Vec<Version>> {
+        let url = self.url().clone();
+        let mut versions = Vec::new();
+        for response in self.multistatus()?.response {
+            let (href, propstats) = match response {
+                WebDAVResponse::Propstat { href, propstat, .. } => (href, propstat),
+                WebDAVResponse::Status { href, status, .. } => {
+                    warn!("version {} has status {}", href.head.0, status);
                     continue;
                 }
             };
-            debug!(
-                "found {} properties for {}",
-                propstats.len(),
-                path.0.to_string()
-            );
-            for props in propstats.map(|x| x.prop) {
-                let mut metadata = Metadata::default();
-                if let Some(Some(Ok(date))) = props.creationdate() {
-                    debug!("creation date: {:?}", date.0);
-                    metadata.created = Some(date.0.into());
+            let mut props = Properties::default();
+            for propstat in propstats {
+                if propstat.status.0.is_success() {
+                    props.merge(propstat.prop);
                 }
-                if let Some(Some(Ok(date))) = props.getlastmodified() {
-                    debug!("last modified: {:?}", date.0);
-                    metadata.modified = Some(date.0.into());
+            }
+
+            let mut version = Version {
+                url: resolve(&url, &href)?,
+                name: None,
+                creator: None,
+                modified: None,
+            };
+            match props.get::<VersionName>() {
+                Some(Some(Ok(name))) => version.name = Some(name.0.to_string()),
+                Some(Some(Err(err))) => warn!("invalid version-name of {}: {err}", href.0),
+                _ => {}
+            }
+            match props.get::<CreatorDisplayName>() {
+                Some(Some(Ok(creator))) => version.creator = Some(creator.0.to_string()),
+                Some(Some(Err(err))) => warn!("invalid creator-displayname of {}: {err}", href.0),
+                _ => {}
+            }
+            match props.getlastmodified() {
+                Some(Some(Ok(date))) => version.modified = Some(date.0.into()),
+                Some(Some(Err(err))) => warn!("invalid last modified date of {}: {err}", href.0),
+                _ => {}
+            }
+            versions.push(version);
+        }
+
+        Ok(versions)
+    }
+
+    /// Parse the `acl` property of the first resource of a multistatus response.
+    ///
+    /// The property is reported with an error status if the user is not allowed to read the ACL (`read-acl`
+    /// privilege); a missing property means that the server doesn't support access control.
+    pub fn acl(self) -> RemoteResult<Vec<Ace>> {
+        let method = self.method.clone();
+        let (href, propstats) = self.propstats()?;
+        let mut failed = None;
+        for propstat in propstats {
+            match propstat.prop.acl() {
+                None => continue,
+                Some(_) if !propstat.status.0.is_success() => failed = Some(propstat),
+                Some(Some(Ok(acl))) => return Ok(acl.0),
+                Some(None) => return Ok(Vec::new()),
+                Some(Some(Err(err))) => {
+                    return Err(RemoteError::new_ex(
+                        RemoteErrorType::ProtocolError,
+                        format!("invalid acl: {err}"),
+                    ))
                 }
-                if let Some(Some(Ok(size))) = props.getcontentlength() {
-                    debug!("size: {:?}", size.0);
-                    metadata.size = size.0;
+            }
+        }
+
+        Err(match failed {
+            Some(propstat) => Self::status_error(
+                method,
+                href.0.to_string(),
+                propstat.status,
+                &propstat.error,
+                &propstat.responsedescription,
+            ),
+            None => RemoteError::new_ex(
+                RemoteErrorType::UnsupportedFeature,
+                "acl property not reported",
+            ),
+        })
+    }
+
+    /// Merge the properties of the first resource of a multistatus response which have been reported with a
+    /// successful status
+    fn properties(self) -> RemoteResult<Properties> {
+        let mut props = Properties::default();
+        for propstat in self.propstats()?.1 {
+            if propstat.status.0.is_success() {
+                props.merge(propstat.prop);
+            }
+        }
+
+        Ok(props)
+    }
+
+    /// Get the propstats of the first resource of a multistatus response, failing if the server reported a
+    /// single error status for the resource
+    fn propstats(self) -> RemoteResult<(Href, NonEmpty<Propstat>)> {
+        let method = self.method.clone();
+        match self.multistatus()?.response.into_iter().next() {
+            Some(WebDAVResponse::Propstat { href, propstat, .. }) => Ok((href, propstat)),
+            Some(WebDAVResponse::Status {
+                href,
+                status,
+                error,
+                responsedescription,
+            }) => Err(Self::status_error(
+                method,
+                href.head.0.to_string(),
+                status,
+                &error,
+                &responsedescription,
+            )),
+            None => Err(RemoteError::new_ex(
+                RemoteErrorType::ProtocolError,
+                "empty multistatus",
+            )),
+        }
+    }
+
+    /// Parse the outcome of each property of a `PROPPATCH` request.
+    ///
+    /// `requested` are the properties sent in the request; they are all reported as updated if the server
+    /// answered with a successful status other than `207 Multi-Status`, or with the error of a `response`
+    /// reporting a single status for the whole resource.
+    pub fn property_results(
+        mut self,
+        requested: Vec<(Option<String>, String)>,
+    ) -> RemoteResult<Vec<PropertyResult>> {
+        self.status()?;
+        if self.response.status().as_u16() != http::StatusCode::MULTI_STATUS.as_u16() {
+            return Ok(requested
+                .into_iter()
+                .map(|(namespace, name)| PropertyResult {
+                    namespace,
+                    name,
+                    result: Ok(()),
+                })
+                .collect());
+        }
+        let method = self.method.clone();
+        let multistatus = self.multistatus()?;
+
+        Ok(Self::property_results_of(method, multistatus, &requested))
+    }
+
+    /// Collect the outcome of each property in `multistatus`
+    fn property_results_of(
+        method: Method,
+        multistatus: Multistatus,
+        requested: &[(Option<String>, String)],
+    ) -> Vec<PropertyResult> {
+        let mut results = Vec::new();
+        for response in multistatus.response {
+            match response {
+                WebDAVResponse::Propstat { href, propstat, .. } => {
+                    for propstat in propstat {
+                        let result = if propstat.status.0.is_success() {
+                            Ok(())
+                        } else {
+                            Err(Self::status_error(
+                                method.clone(),
+                                href.0.to_string(),
+                                propstat.status,
+                                &propstat.error,
+                                &propstat.responsedescription,
+                            ))
+                        };
+                        results.extend(propstat.prop.names().map(|(namespace, name)| {
+                            PropertyResult {
+                                namespace: namespace.map(str::to_string),
+                                name: name.to_string(),
+                                result: result.clone(),
+                            }
+                        }));
+                    }
                 }
-                let file_name = path.0.to_string();
-                let path = PathBuf::from(path.0.to_string());
-                if file_name.ends_with('/') || path.is_dir() {
-                    debug!("path {} is a directory", path.display());
-                    metadata.file_type = FileType::Directory;
-                } else {
-                    debug!("path {} is a file", path.display());
-                    metadata.file_type = FileType::File;
+                WebDAVResponse::Status {
+                    href,
+                    status,
+                    error,
+                    responsedescription,
+                } => {
+                    let error = Self::status_error(
+                        method.clone(),
+                        href.head.0.to_string(),
+                        status,
+                        &error,
+                        &responsedescription,
+                    );
+                    results.extend(requested.iter().map(|(namespace, name)| PropertyResult {
+                        namespace: namespace.clone(),
+                        name: name.clone(),
+                        result: Err(error.clone()),
+                    }));
                 }
+            }
+        }
+
+        results
+    }
+
+    pub fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.response.read(buf)
+    }
+
+    fn parse_multistatus(bytes: impl Into<bytes::Bytes>) -> RemoteResult<Multistatus> {
+        let multistatus = Multistatus::from_xml(bytes)
+            .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        debug!("parsed multistatus: {:?}", multistatus);
+
+        Ok(multistatus)
+    }
 
-                files.push(File { path, metadata });
+    #[cfg(test)]
+    fn parse_propfind(bytes: &'static [u8]) -> RemoteResult<Listing> {
+        Entries::new(bytes).into_listing()
+    }
+
+    /// Build the entry of a `response` of a multistatus listing, or the errors of the entries it reports with an
+    /// unsuccessful status
+    fn entry(response: WebDAVResponse) -> Result<Entry, Vec<EntryError>> {
+        let (path, propstats) = match response {
+            WebDAVResponse::Propstat {
+                href: path,
+                propstat,
+                ..
+            } => (path, propstat),
+            WebDAVResponse::Status {
+                href,
+                status,
+                error,
+                responsedescription,
+            } => {
+                return Err(href
+                    .into_iter()
+                    .map(|href| {
+                        debug!("entry {} has status {}", href.0, status);
+                        Self::entry_error(href, status, &error, &responsedescription)
+                    })
+                    .collect());
+            }
+        };
+        debug!(
+            "found {} properties for {}",
+            propstats.len(),
+            path.0.to_string()
+        );
+        // merge properties of successful propstats; properties reported with an error status (e.g. 404 or 403)
+        // are absent
+        let mut props = Properties::default();
+        for propstat in propstats {
+            if propstat.status.0.is_success() {
+                props.merge(propstat.prop);
+            } else {
+                debug!("ignoring properties with status {}", propstat.status);
+            }
+        }
+
+        let mut metadata = Metadata::default();
+        match props.creationdate() {
+            Some(Some(Ok(date))) => {
+                debug!("creation date: {:?}", date.0);
+                metadata.created = Some(date.0.into());
             }
+            Some(Some(Err(err))) => warn!("invalid creation date of {}: {err}", path.0),
+            _ => {}
+        }
+        match props.getlastmodified() {
+            Some(Some(Ok(date))) => {
+                debug!("last modified: {:?}", date.0);
+                metadata.modified = Some(date.0.into());
+            }
+            Some(Some(Err(err))) => warn!("invalid last modified date of {}: {err}", path.0),
+            _ => {}
+        }
+        if let Some(Some(Ok(size))) = props.getcontentlength() {
+            debug!("size: {:?}", size.0);
+            metadata.size = size.0;
+        }
+        let is_collection = match props.resourcetype() {
+            Some(Some(Ok(resourcetype))) => resourcetype.is_collection(),
+            Some(None) => false,
+            // servers which don't report the resource type still end the href of collections with a slash
+            Some(Some(Err(_))) | None => path.0.path().ends_with('/'),
+        };
+        let path = PathBuf::from(path.0.to_string());
+        if is_collection {
+            debug!("path {} is a directory", path.display());
+            metadata.file_type = FileType::Directory;
+        } else {
+            debug!("path {} is a file", path.display());
+            metadata.file_type = FileType::File;
+            if let Some(Some(Ok(executable))) = props.executable() {
+                debug!("executable: {:?}", executable.0);
+                metadata.mode = Some(UnixPex::from(if executable.0 {
+                    EXECUTABLE_FILE_MODE
+                } else {
+                    FILE_MODE
+                }));
+            }
+        }
+        let owner = match props.owner() {
+            Some(Some(Ok(owner))) => Some(owner.0.to_string()),
+            _ => None,
+        };
+        let group = match props.group() {
+            Some(Some(Ok(group))) => Some(group.0.to_string()),
+            _ => None,
+        };
+        let locks = match props.lockdiscovery() {
+            Some(Some(Ok(lockdiscovery))) => lockdiscovery.0,
+            Some(Some(Err(err))) => {
+                warn!("invalid lockdiscovery of {}: {err}", path.display());
+                Vec::new()
+            }
+            _ => Vec::new(),
+        };
+        let supported_locks = match props.supportedlock() {
+            Some(Some(Ok(supportedlock))) => supportedlock.0,
+            Some(Some(Err(err))) => {
+                warn!("invalid supportedlock of {}: {err}", path.display());
+                Vec::new()
+            }
+            _ => Vec::new(),
+        };
+        // an empty privilege set means the user has no privilege at all
+        let privileges = match props.current_user_privilege_set() {
+            Some(Some(Ok(set))) => Some(set.0),
+            Some(None) => Some(Vec::new()),
+            Some(Some(Err(err))) => {
+                warn!(
+                    "invalid current-user-privilege-set of {}: {err}",
+                    path.display()
+                );
+                None
+            }
+            None => None,
+        };
+        if let Some(privileges) = &privileges {
+            let mode = privileges_mode(privileges, &metadata.file_type, metadata.mode);
+            debug!("mode from privileges: {:o}", u32::from(mode));
+            metadata.mode = Some(mode);
+        }
+
+        Ok(Entry {
+            file: File { path, metadata },
+            owner,
+            group,
+            locks,
+            supported_locks,
+            privileges,
+        })
+    }
+
+    /// Build the error for an entry of a multistatus listing reported with an unsuccessful `status`
+    fn entry_error(
+        href: Href,
+        status: Status,
+        error: &Option<ErrorElement>,
+        description: &Option<ResponseDescription>,
+    ) -> EntryError {
+        EntryError {
+            path: PathBuf::from(href.0.to_string()),
+            error: Self::status_error(
+                method("PROPFIND"),
+                href.0.to_string(),
+                status,
+                error,
+                description,
+            ),
+        }
+    }
+
+    /// Build the error for an unsuccessful `status` reported in a multistatus response to a request with `method`
+    fn status_error(
+        method: Method,
+        url: String,
+        status: Status,
+        error: &Option<ErrorElement>,
+        description: &Option<ResponseDescription>,
+    ) -> RemoteError {
+        let (mut kind, reason) = status_error_kind(status.0.as_u16(), &method);
+        if let Some(condition_kind) = error.as_ref().and_then(Self::condition_kind) {
+            kind = condition_kind;
         }
+        let body = match (error, description) {
+            (Some(error), _) => Some(error.to_string()),
+            (None, Some(description)) => Some(description.0.to_string()),
+            (None, None) => None,
+        };
+        let error = StatusError {
+            method,
+            url,
+            status: status.0,
+            reason,
+            location: None,
+            body,
+        };
 
-        Ok(files)
+        RemoteError::new_ex(kind, error)
+    }
+}
+
+/// Get the error kind and the reason for an unsuccessful `status` returned for a request with `method`
+fn status_error_kind(status: u16, method: &Method) -> (RemoteErrorType, Option<&'static str>) {
+    match (status, method) {
+        (401, _) => (RemoteErrorType::AuthenticationFailed, None),
+        (403, _) => (RemoteErrorType::CouldNotOpenFile, None),
+        (400 | 404, _) => (RemoteErrorType::NoSuchFileOrDirectory, None),
+        (405, method) if method.as_str() == "MKCOL" => (
+            RemoteErrorType::DirectoryAlreadyExists,
+            Some("already exists"),
+        ),
+        (405, _) => (RemoteErrorType::UnsupportedFeature, None),
+        (409, _) => (
+            RemoteErrorType::NoSuchFileOrDirectory,
+            Some("parent collection does not exist"),
+        ),
+        (412, _) => (RemoteErrorType::ProtocolError, Some("precondition failed")),
+        (413, _) => (RemoteErrorType::FileCreateDenied, Some("too large")),
+        (423, _) => (RemoteErrorType::PexError, Some("resource is locked")),
+        (424, _) => (RemoteErrorType::ProtocolError, Some("failed dependency")),
+        (507, _) => (RemoteErrorType::IoError, Some("insufficient storage")),
+        (501, _) => (RemoteErrorType::UnsupportedFeature, Some("not implemented")),
+        (300..=399, _) => (RemoteErrorType::ProtocolError, Some("unexpected redirect")),
+        _ => (RemoteErrorType::ProtocolError, None),
+    }
+}
+
+/// Context of a request which failed with an unsuccessful status
+struct StatusError {
+    method: Method,
+    url: String,
+    status: http::StatusCode,
+    reason: Option<&'static str>,
+    location: Option<String>,
+    body: Option<String>,
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} returned {}", self.method, self.url, self.status)?;
+        if let Some(reason) = self.reason {
+            write!(f, " ({reason})")?;
+        }
+        if let Some(location) = &self.location {
+            write!(f, " to {location}")?;
+        }
+        if let Some(body) = &self.body {
+            write!(f, ": {body}")?;
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod test {
 
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, SystemTime};
+
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::webdav_xml::elements::{Depth, LockRoot, LockScope, LockToken, LockType, Timeout};
+    use crate::webdav_xml::properties::{CreationDate, LastModified, LockDiscovery, Owner};
+    use crate::webdav_xml::Value;
 
     #[test]
     fn test_should_parse_dir_content() {
@@ -204,7 +1181,24 @@ mod test {
         
 "#;
 
-        let files = ResponseParser::parse_propfind(response.as_bytes()).unwrap();
+        let entries = ResponseParser::parse_propfind(response.as_bytes())
+            .unwrap()
+            .entries;
+        assert!(entries.iter().all(|entry| entry.locks.is_empty()));
+        assert_eq!(
+            entries[2].supported_locks,
+            vec![
+                LockEntry {
+                    lockscope: LockScope::Exclusive,
+                    locktype: LockType::Write,
+                },
+                LockEntry {
+                    lockscope: LockScope::Shared,
+                    locktype: LockType::Write,
+                },
+            ]
+        );
+        let files: Vec<File> = entries.into_iter().map(|entry| entry.file).collect();
         assert_eq!(files.len(), 3);
         let ciao_dir = &files[0];
         assert!(ciao_dir.is_dir());
@@ -218,5 +1212,717 @@ mod test {
         assert!(build_rs.is_file());
         assert_eq!(build_rs.path, PathBuf::from("/ciao/build.rs"));
         assert_eq!(build_rs.metadata.size, 486);
+        assert_eq!(build_rs.metadata.mode, Some(UnixPex::from(0o644)));
+    }
+
+    #[test]
+    fn test_should_detect_collections_from_resourcetype() {
+        let response = r#"<?xml version="1.0" encoding="utf-8"?>
+        <D:multistatus xmlns:D="DAV:">
+        <D:response><D:href>/docs</D:href><D:propstat>
+        <D:prop><D:resourcetype><D:collection/></D:resourcetype></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>
+        <D:response><D:href>/docs/a.txt</D:href><D:propstat>
+        <D:prop><D:resourcetype/></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>
+        <D:response><D:href>/docs/b/</D:href><D:propstat>
+        <D:prop><D:getcontentlength>1</D:getcontentlength></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>
+        </D:multistatus>"#;
+        let listing = ResponseParser::parse_propfind(response.as_bytes()).unwrap();
+        let dirs: Vec<_> = listing
+            .entries
+            .iter()
+            .map(|entry| entry.file.is_dir())
+            .collect();
+        // without `resourcetype`, the trailing slash of the href is trusted
+        assert_eq!(dirs, vec![true, false, true]);
+    }
+
+    #[test]
+    fn test_should_merge_propstats() {
+        let response = r#"
+        <?xml version="1.0" encoding="utf-8"?>
+        <d:multistatus xmlns:d="DAV:">
+        <d:response>
+        <d:href>/files/build.rs</d:href>
+        <d:propstat>
+        <d:prop>
+        <d:resourcetype/>
+        <d:getlastmodified>Sat, 02 Mar 2024 15:44:46 GMT</d:getlastmodified>
+        </d:prop>
+        <d:status>HTTP/1.1 200 OK</d:status>
+        </d:propstat>
+        <d:propstat>
+        <d:prop>
+        <d:getcontentlength>486</d:getcontentlength>
+        </d:prop>
+        <d:status>HTTP/1.1 200 OK</d:status>
+        </d:propstat>
+        <d:propstat>
+        <d:prop>
+        <d:creationdate/>
+        <d:quota-used-bytes/>
+        </d:prop>
+        <d:status>HTTP/1.1 404 Not Found</d:status>
+        </d:propstat>
+        <d:propstat>
+        <d:prop>
+        <d:creationdate>2024-03-02T15:44:46Z</d:creationdate>
+        </d:prop>
+        <d:status>HTTP/1.1 403 Forbidden</d:status>
+        </d:propstat>
+        </d:response>
+        </d:multistatus>
+"#;
+
+        let files: Vec<File> = ResponseParser::parse_propfind(response.as_bytes())
+            .unwrap()
+            .entries
+            .into_iter()
+            .map(|entry| entry.file)
+            .collect();
+        assert_eq!(files.len(), 1);
+        let build_rs = &files[0];
+        assert!(build_rs.is_file());
+        assert_eq!(build_rs.path, PathBuf::from("/files/build.rs"));
+        assert_eq!(build_rs.metadata.size, 486);
+        assert!(build_rs.metadata.modified.is_some());
+        assert_eq!(build_rs.metadata.created, None);
+    }
+
+    #[test]
+    fn test_should_parse_executable_and_owner() {
+        let response = r#"
+        <?xml version="1.0" encoding="utf-8"?>
+        <D:multistatus xmlns:D="DAV:">
+        <D:response xmlns:lp2="http://apache.org/dav/props/" xmlns:lp1="DAV:">
+        <D:href>/deploy/run.sh</D:href>
+        <D:propstat>
+        <D:prop>
+        <lp1:resourcetype/>
+        <lp2:executable>T</lp2:executable>
+        <D:owner><D:href>/principals/users/alice</D:href></D:owner>
+        <D:group><D:href>/principals/groups/deploy</D:href></D:group>
+        </D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response xmlns:lp2="http://apache.org/dav/props/" xmlns:lp1="DAV:">
+        <D:href>/deploy/data.csv</D:href>
+        <D:propstat>
+        <D:prop>
+        <lp1:resourcetype/>
+        <lp2:executable>F</lp2:executable>
+        <D:owner>alice</D:owner>
+        </D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/deploy/notes.txt</D:href>
+        <D:propstat>
+        <D:prop><D:resourcetype/></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        </D:multistatus>
+"#;
+
+        let entries = ResponseParser::parse_propfind(response.as_bytes())
+            .unwrap()
+            .entries;
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].file.metadata.mode, Some(UnixPex::from(0o755)));
+        assert_eq!(entries[0].owner.as_deref(), Some("/principals/users/alice"));
+        assert_eq!(
+            entries[0].group.as_deref(),
+            Some("/principals/groups/deploy")
+        );
+        assert_eq!(entries[1].file.metadata.mode, Some(UnixPex::from(0o644)));
+        assert_eq!(entries[1].owner.as_deref(), Some("alice"));
+        assert_eq!(entries[1].group, None);
+        assert_eq!(entries[2].file.metadata.mode, None);
+        assert_eq!(entries[2].owner, None);
+    }
+
+    #[test]
+    fn test_should_map_current_user_privileges_to_mode() {
+        let response = r#"
+        <?xml version="1.0" encoding="utf-8"?>
+        <D:multistatus xmlns:D="DAV:" xmlns:A="http://apache.org/dav/props/" xmlns:X="urn:example">
+        <D:response>
+        <D:href>/deploy/run.sh</D:href>
+        <D:propstat>
+        <D:prop>
+        <D:resourcetype/>
+        <A:executable>T</A:executable>
+        <D:current-user-privilege-set><D:privilege><D:read/></D:privilege></D:current-user-privilege-set>
+        </D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/deploy/data.csv</D:href>
+        <D:propstat>
+        <D:prop>
+        <D:resourcetype/>
+        <D:current-user-privilege-set>
+        <D:privilege><D:read/></D:privilege>
+        <D:privilege><D:write-content/></D:privilege>
+        <D:privilege><X:share/></D:privilege>
+        </D:current-user-privilege-set>
+        </D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/deploy/shared/</D:href>
+        <D:propstat>
+        <D:prop>
+        <D:resourcetype><D:collection/></D:resourcetype>
+        <D:current-user-privilege-set>
+        <D:privilege><D:read/></D:privilege>
+        <D:privilege><D:bind/></D:privilege>
+        <D:privilege><D:unbind/></D:privilege>
+        </D:current-user-privilege-set>
+        </D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/deploy/inbox/</D:href>
+        <D:propstat>
+        <D:prop>
+        <D:resourcetype><D:collection/></D:resourcetype>
+        <D:current-user-privilege-set><D:privilege><D:bind/></D:privilege></D:current-user-privilege-set>
+        </D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/deploy/owned.txt</D:href>
+        <D:propstat>
+        <D:prop>
+        <D:resourcetype/>
+        <D:current-user-privilege-set><D:privilege><D:all/></D:privilege></D:current-user-privilege-set>
+        </D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/deploy/secret.txt</D:href>
+        <D:propstat>
+        <D:prop><D:resourcetype/><D:current-user-privilege-set/></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/deploy/notes.txt</D:href>
+        <D:propstat>
+        <D:prop><D:resourcetype/></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        <D:propstat>
+        <D:prop><D:current-user-privilege-set/></D:prop>
+        <D:status>HTTP/1.1 404 Not Found</D:status>
+        </D:propstat>
+        </D:response>
+        </D:multistatus>
+"#;
+
+        let entries = ResponseParser::parse_propfind(response.as_bytes())
+            .unwrap()
+            .entries;
+        assert_eq!(entries.len(), 7);
+        // the execute bit and the group and others bits come from the executable property
+        assert_eq!(entries[0].file.metadata.mode, Some(UnixPex::from(0o555)));
+        assert_eq!(entries[0].privileges, Some(vec![Privilege::Read]));
+        assert_eq!(entries[1].file.metadata.mode, Some(UnixPex::from(0o600)));
+        assert_eq!(
+            entries[1].privileges,
+            Some(vec![
+                Privilege::Read,
+                Privilege::WriteContent,
+                Privilege::Other {
+                    namespace: Some("urn:example".into()),
+                    name: "share".into()
+                }
+            ])
+        );
+        assert_eq!(entries[2].file.metadata.mode, Some(UnixPex::from(0o700)));
+        // binding without unbinding doesn't make a directory writable
+        assert_eq!(entries[3].file.metadata.mode, Some(UnixPex::from(0o000)));
+        assert_eq!(entries[4].file.metadata.mode, Some(UnixPex::from(0o600)));
+        assert_eq!(entries[5].file.metadata.mode, Some(UnixPex::from(0o000)));
+        assert_eq!(entries[5].privileges, Some(Vec::new()));
+        assert_eq!(entries[6].file.metadata.mode, None);
+        assert_eq!(entries[6].privileges, None);
+    }
+
+    #[test]
+    fn test_should_parse_lockdiscovery() {
+        let response = r#"<?xml version="1.0" encoding="utf-8"?>
+        <d:multistatus xmlns:d="DAV:">
+        <d:response>
+        <d:href>/docs/report.odt</d:href>
+        <d:propstat>
+        <d:prop>
+        <d:resourcetype/>
+        <d:lockdiscovery>
+        <d:activelock>
+        <d:lockscope><d:exclusive/></d:lockscope>
+        <d:locktype><d:write/></d:locktype>
+        <d:depth>infinity</d:depth>
+        <d:owner><d:href>mailto:alice@example.com</d:href></d:owner>
+        <d:timeout>Second-3600</d:timeout>
+        <d:locktoken><d:href>urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6</d:href></d:locktoken>
+        <d:lockroot><d:href>/docs/report.odt</d:href></d:lockroot>
+        </d:activelock>
+        <d:activelock>
+        <d:lockscope><d:shared/></d:lockscope>
+        <d:locktype><d:write/></d:locktype>
+        <d:depth>0</d:depth>
+        <d:owner><x:person xmlns:x="urn:example">Bob</x:person></d:owner>
+        <d:timeout>Infinite</d:timeout>
+        </d:activelock>
+        </d:lockdiscovery>
+        </d:prop>
+        <d:status>HTTP/1.1 200 OK</d:status>
+        </d:propstat>
+        </d:response>
+        </d:multistatus>"#;
+
+        let entries = ResponseParser::parse_propfind(response.as_bytes())
+            .unwrap()
+            .entries;
+        assert_eq!(entries.len(), 1);
+        let locks = &entries[0].locks;
+        assert_eq!(locks.len(), 2);
+        assert_eq!(
+            locks[0],
+            ActiveLock {
+                lockscope: LockScope::Exclusive,
+                locktype: LockType::Write,
+                depth: Depth::Infinity,
+                owner: Some(Owner("mailto:alice@example.com".into())),
+                timeout: Some(Timeout::Seconds(3600)),
+                locktoken: Some(LockToken(
+                    "urn:uuid:e71d4fae-5dec-11d0-a765-00a0c91e6bf6".into()
+                )),
+                lockroot: Some(LockRoot("/docs/report.odt".parse().unwrap())),
+            }
+        );
+        assert_eq!(locks[1].lockscope, LockScope::Shared);
+        assert_eq!(locks[1].depth, Depth::Zero);
+        // owner with custom markup is ignored
+        assert_eq!(locks[1].owner, None);
+        assert_eq!(locks[1].timeout, Some(Timeout::Infinite));
+        assert_eq!(locks[1].locktoken, None);
+
+        // serialize and parse again
+        let lockdiscovery = LockDiscovery(vec![locks[0].clone()]);
+        assert_eq!(
+            LockDiscovery::try_from(&Value::from(lockdiscovery.clone())).unwrap(),
+            lockdiscovery
+        );
+    }
+
+    /// A multistatus body of `count` responses, generated as it is read
+    struct HugeListing {
+        count: usize,
+        next: usize,
+        chunk: io::Cursor<Vec<u8>>,
+        read: Arc<AtomicUsize>,
+    }
+
+    impl HugeListing {
+        fn new(count: usize, read: Arc<AtomicUsize>) -> Self {
+            let header = r#"<?xml version="1.0"?><D:multistatus xmlns:D="DAV:">"#;
+            Self {
+                count,
+                next: 0,
+                chunk: io::Cursor::new(header.as_bytes().to_vec()),
+                read,
+            }
+        }
+    }
+
+    impl io::Read for HugeListing {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.chunk.read(buf)?;
+            if n > 0 {
+                self.read.fetch_add(n, Ordering::SeqCst);
+                return Ok(n);
+            }
+            let chunk = match self.next {
+                next if next < self.count => format!(
+                    "<D:response><D:href>/huge/file-{next}.txt</D:href><D:propstat>\
+                     <D:prop><D:getcontentlength>{next}</D:getcontentlength></D:prop>\
+                     <D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>"
+                ),
+                next if next == self.count => String::from("</D:multistatus>"),
+                _ => return Ok(0),
+            };
+            self.next += 1;
+            self.chunk = io::Cursor::new(chunk.into_bytes());
+            self.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_should_stream_entries() {
+        let read = Arc::new(AtomicUsize::new(0));
+        let mut entries = Entries::new(BufReader::new(HugeListing::new(10_000, read.clone())));
+        let first: Vec<_> = entries.by_ref().take(2).map(Result::unwrap).collect();
+        assert_eq!(first[0].file.path, PathBuf::from("/huge/file-0.txt"));
+        assert_eq!(first[1].file.path, PathBuf::from("/huge/file-1.txt"));
+        assert_eq!(first[1].file.metadata.size, 1);
+        // only the first responses have been read
+        assert!(read.load(Ordering::SeqCst) < 64 * 1024);
+
+        assert_eq!(entries.count(), 9_998);
+    }
+
+    #[test]
+    fn test_should_stop_streaming_at_malformed_body() {
+        let response = r#"<?xml version="1.0"?>
+        <D:multistatus xmlns:D="DAV:">
+        <D:response>
+        <D:href>/docs/a.txt</D:href>
+        <D:propstat><D:prop><D:getcontentlength>1</D:getcontentlength></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status></D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/docs/b.txt</D:href>
+        <D:propstat><D:prop><D:getcontentlength>2</D:getcontentlength></D:prop>"#;
+        let mut entries = Entries::new(response.as_bytes());
+        assert_eq!(
+            entries.next().unwrap().unwrap().file.path,
+            PathBuf::from("/docs/a.txt")
+        );
+        assert_eq!(
+            entries.next().unwrap().unwrap_err().kind,
+            RemoteErrorType::ProtocolError
+        );
+        assert!(entries.next().is_none());
+
+        let err =
+            ResponseParser::parse_propfind(b"<html><body>Not a listing</body></html>").unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::ProtocolError);
+        assert_eq!(
+            ResponseParser::parse_propfind(b"<D:multistatus xmlns:D=\"DAV:\"/>").unwrap(),
+            Listing::default()
+        );
+    }
+
+    #[test]
+    fn test_should_collect_entry_errors() {
+        let response = r#"
+        <?xml version="1.0" encoding="utf-8"?>
+        <d:multistatus xmlns:d="DAV:">
+        <d:response>
+        <d:href>/files/</d:href>
+        <d:propstat>
+        <d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop>
+        <d:status>HTTP/1.1 200 OK</d:status>
+        </d:propstat>
+        </d:response>
+        <d:response>
+        <d:href>/files/private/</d:href>
+        <d:status>HTTP/1.1 403 Forbidden</d:status>
+        </d:response>
+        <d:response>
+        <d:href>/files/mount/</d:href>
+        <d:href>/files/mount2/</d:href>
+        <d:status>HTTP/1.1 500 Internal Server Error</d:status>
+        <d:responsedescription>dangling mount</d:responsedescription>
+        </d:response>
+        </d:multistatus>
+"#;
+
+        let listing = ResponseParser::parse_propfind(response.as_bytes()).unwrap();
+        assert_eq!(listing.entries.len(), 1);
+        assert_eq!(listing.errors.len(), 3);
+        assert_eq!(listing.errors[0].path, PathBuf::from("/files/private/"));
+        assert_eq!(
+            listing.errors[0].error.kind,
+            RemoteErrorType::CouldNotOpenFile
+        );
+        assert_eq!(
+            listing.errors[0].error.msg.as_deref().unwrap(),
+            "PROPFIND /files/private/ returned 403 Forbidden"
+        );
+        assert_eq!(listing.errors[1].path, PathBuf::from("/files/mount/"));
+        assert_eq!(listing.errors[2].path, PathBuf::from("/files/mount2/"));
+        assert_eq!(
+            listing.errors[2].error.msg.as_deref().unwrap(),
+            "PROPFIND /files/mount2/ returned 500 Internal Server Error: dangling mount"
+        );
+    }
+
+    #[test]
+    fn test_should_parse_entries_with_mixed_content() {
+        let response = r#"<?xml version="1.0" encoding="utf-8"?>
+        <D:multistatus xmlns:D="DAV:">
+        <D:response>
+        <D:href>/docs/a.txt</D:href>
+        <D:propstat>
+        <D:prop>
+        <D:owner>Alice <D:href>mailto:alice@example.com</D:href></D:owner>
+        <D:lockdiscovery><D:activelock>
+        <D:locktype><D:write/></D:locktype><D:lockscope><D:exclusive/></D:lockscope>
+        <D:depth>0</D:depth>
+        <D:owner>Alice <D:href>mailto:alice@example.com</D:href></D:owner>
+        <D:locktoken><D:href>urn:uuid:a</D:href></D:locktoken>
+        </D:activelock></D:lockdiscovery>
+        </D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/docs/b.txt</D:href>
+        <D:propstat><D:prop><D:getcontentlength>2</D:getcontentlength></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status></D:propstat>
+        </D:response>
+        </D:multistatus>"#;
+
+        let listing = ResponseParser::parse_propfind(response.as_bytes()).unwrap();
+        assert!(listing.errors.is_empty());
+        assert_eq!(listing.entries.len(), 2);
+        assert_eq!(
+            listing.entries[0].owner.as_deref(),
+            Some("mailto:alice@example.com")
+        );
+        assert_eq!(listing.entries[1].file.path, PathBuf::from("/docs/b.txt"));
+    }
+
+    #[test]
+    fn test_should_collect_invalid_responses() {
+        let response = r#"<?xml version="1.0" encoding="utf-8"?>
+        <D:multistatus xmlns:D="DAV:">
+        <D:response>
+        <D:href>/docs/a.txt</D:href>
+        </D:response>
+        <D:response>
+        <D:href>/docs/b.txt</D:href>
+        <D:propstat><D:prop><D:getcontentlength>2</D:getcontentlength></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status></D:propstat>
+        </D:response>
+        </D:multistatus>"#;
+
+        let listing = ResponseParser::parse_propfind(response.as_bytes()).unwrap();
+        assert_eq!(listing.entries.len(), 1);
+        assert_eq!(listing.entries[0].file.path, PathBuf::from("/docs/b.txt"));
+        assert_eq!(listing.errors.len(), 1);
+        assert_eq!(listing.errors[0].path, PathBuf::from("/docs/a.txt"));
+        assert_eq!(listing.errors[0].error.kind, RemoteErrorType::ProtocolError);
+
+        let mut entries = Entries::new(response.as_bytes()).strict(true);
+        assert_eq!(
+            entries.next().unwrap().unwrap_err().kind,
+            RemoteErrorType::ProtocolError
+        );
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn test_should_parse_sync_changes() {
+        let response = r#"
+        <?xml version="1.0" encoding="utf-8"?>
+        <d:multistatus xmlns:d="DAV:">
+        <d:response>
+        <d:href>/files/report.odt</d:href>
+        <d:propstat>
+        <d:prop><d:resourcetype/><d:getcontentlength>1024</d:getcontentlength></d:prop>
+        <d:status>HTTP/1.1 200 OK</d:status>
+        </d:propstat>
+        </d:response>
+        <d:response>
+        <d:href>/files/old.txt</d:href>
+        <d:status>HTTP/1.1 404 Not Found</d:status>
+        </d:response>
+        <d:response>
+        <d:href>/files/private/</d:href>
+        <d:status>HTTP/1.1 403 Forbidden</d:status>
+        </d:response>
+        <d:response>
+        <d:href>/files/</d:href>
+        <d:status>HTTP/1.1 507 Insufficient Storage</d:status>
+        <d:error><d:number-of-matches-within-limits/></d:error>
+        </d:response>
+        <d:sync-token>http://example.com/sync/42</d:sync-token>
+        </d:multistatus>
+"#;
+
+        let changes = ResponseParser::sync_changes(response.as_bytes(), None).unwrap();
+        assert_eq!(changes.entries.len(), 1);
+        assert_eq!(
+            changes.entries[0].file.path,
+            PathBuf::from("/files/report.odt")
+        );
+        assert_eq!(changes.entries[0].file.metadata.size, 1024);
+        assert_eq!(changes.removed, vec![PathBuf::from("/files/old.txt")]);
+        assert_eq!(changes.errors.len(), 1);
+        assert_eq!(changes.errors[0].path, PathBuf::from("/files/private/"));
+        assert_eq!(changes.token, "http://example.com/sync/42");
+        assert!(changes.truncated);
+
+        // the token is mandatory
+        let response = r#"<d:multistatus xmlns:d="DAV:"></d:multistatus>"#;
+        let err = ResponseParser::sync_changes(response.as_bytes(), None).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::ProtocolError);
+    }
+
+    #[test]
+    fn test_should_parse_error_condition() {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?>
+        <D:error xmlns:D="DAV:">
+        <D:lock-token-submitted>
+        <D:href>/workspace/webdav/</D:href>
+        </D:lock-token-submitted>
+        </D:error>"#;
+        let error = ResponseParser::parse_error(&bytes::Bytes::from(body)).unwrap();
+        assert_eq!(
+            error.conditions,
+            vec![Condition::LockTokenSubmitted(vec!["/workspace/webdav/"
+                .parse()
+                .unwrap()])]
+        );
+        assert_eq!(
+            ResponseParser::condition_kind(&error),
+            Some(RemoteErrorType::PexError)
+        );
+        assert_eq!(
+            error.to_string(),
+            "lock-token-submitted (/workspace/webdav/)"
+        );
+    }
+
+    #[test]
+    fn test_should_parse_sabredav_exception() {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?>
+        <d:error xmlns:d="DAV:" xmlns:s="http://sabredav.org/ns">
+        <s:sabredav-version>4.6.0</s:sabredav-version>
+        <s:exception>Sabre\DAV\Exception\Conflict</s:exception>
+        <s:message>Parent node does not exist</s:message>
+        </d:error>"#;
+        let error = ResponseParser::parse_error(&bytes::Bytes::from(body)).unwrap();
+        assert!(error.conditions.is_empty());
+        assert_eq!(ResponseParser::condition_kind(&error), None);
+        assert_eq!(
+            error.to_string(),
+            "Sabre\\DAV\\Exception\\Conflict: Parent node does not exist"
+        );
+    }
+
+    #[test]
+    fn test_should_not_parse_error_from_html() {
+        let body = "<html><body>Not Found</body></html>";
+        assert!(ResponseParser::parse_error(&bytes::Bytes::from(body)).is_none());
+        assert_eq!(
+            ResponseParser::error_text(body.as_bytes()).unwrap(),
+            "<html><body>Not Found</body></html>"
+        );
+    }
+
+    #[test]
+    fn test_should_parse_dates_of_server_fixtures() {
+        let fixtures = [
+            (
+                "apache",
+                include_str!("../tests/fixtures/propfind/apache.xml"),
+            ),
+            (
+                "asctime",
+                include_str!("../tests/fixtures/propfind/asctime.xml"),
+            ),
+            (
+                "fractional",
+                include_str!("../tests/fixtures/propfind/fractional.xml"),
+            ),
+            ("iis", include_str!("../tests/fixtures/propfind/iis.xml")),
+            (
+                "lighttpd",
+                include_str!("../tests/fixtures/propfind/lighttpd.xml"),
+            ),
+            (
+                "nginx",
+                include_str!("../tests/fixtures/propfind/nginx.xml"),
+            ),
+            (
+                "rfc850",
+                include_str!("../tests/fixtures/propfind/rfc850.xml"),
+            ),
+            (
+                "sabredav",
+                include_str!("../tests/fixtures/propfind/sabredav.xml"),
+            ),
+        ];
+        let expected = SystemTime::UNIX_EPOCH + Duration::from_secs(1709394286);
+        let secs = |time: SystemTime| {
+            time.duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        };
+
+        for (name, fixture) in fixtures {
+            let listing = ResponseParser::parse_propfind(fixture.as_bytes()).unwrap();
+            assert_eq!(listing.entries.len(), 1, "{name}");
+            let metadata = &listing.entries[0].file.metadata;
+            assert_eq!(metadata.modified, Some(expected), "{name}");
+            // nginx doesn't report the creation date
+            if name != "nginx" {
+                assert_eq!(metadata.created.map(secs), Some(1709394286), "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_should_parse_lenient_dates() {
+        let dates = [
+            ("2024-03-02T15:44:46Z", 0),
+            ("2024-03-02t15:44:46z", 0),
+            ("2024-03-02T15:44:46", 0),
+            ("2024-03-02 15:44:46", 0),
+            ("2024-03-02T15:44:46.5Z", 500_000_000),
+            ("2024-03-02T15:44:46,123456789123Z", 123_456_789),
+            ("2024-03-02T17:44:46+02:00", 0),
+            ("2024-03-02T10:44:46-0500", 0),
+            ("2024-03-02T16:44:46+01", 0),
+            (" Sat, 02 Mar 2024 15:44:46 GMT ", 0),
+            ("Saturday, 02-Mar-24 15:44:46 GMT", 0),
+            ("Sat Mar  2 15:44:46 2024", 0),
+            ("Sat, 2 Mar 2024 15:44:46 UTC", 0),
+            ("Sat, 02 Mar 2024 16:44:46 +0100", 0),
+            ("02 March 2024 15:44:46", 0),
+        ];
+        for (date, nanos) in dates {
+            let created = CreationDate::try_from(&Value::Text(date.into())).unwrap();
+            assert_eq!(created.0.unix_timestamp(), 1709394286, "{date}");
+            assert_eq!(created.0.nanosecond(), nanos, "{date}");
+            let modified = LastModified::try_from(&Value::Text(date.into())).unwrap();
+            assert_eq!(modified.0, created.0, "{date}");
+        }
+
+        for date in [
+            "",
+            "yesterday",
+            "2024-13-02T15:44:46Z",
+            "2024-02-30T15:44:46Z",
+            "2024-03-02T25:44:46Z",
+            "2024-03-02X15:44:46Z",
+            "Sat, 02 Foo 2024 15:44:46 GMT",
+            "Sat, 02 Mar 2024 GMT",
+            "2024-03-02T15:44:46ü",
+            "2024-03-02T15:44:46+aéb",
+            "2024-03-02T15:44:46-éé",
+            "Sat, 02 Mar 2024 15:44:46 +aéb",
+        ] {
+            assert!(
+                LastModified::try_from(&Value::Text(date.into())).is_err(),
+                "{date}"
+            );
+        }
     }
 }
@@ -0,0 +1,23 @@
+//! ## Principal
+//!
+//! Principal the client is authenticated as (RFC 5397)
+
+use reqwest::Url;
+
+/// The principal the client is authenticated as, as returned by [`crate::WebDAVFs::current_principal`].
+///
+/// The urls are resolved against the url of the principal resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrentPrincipal {
+    /// Url of the principal resource, from the `current-user-principal` property
+    pub url: Url,
+    /// Name of the principal, from the `displayname` property
+    pub display_name: Option<String>,
+    /// Canonical url of the principal resource, from the `principal-URL` property
+    pub principal_url: Option<Url>,
+    /// Collections containing the calendars of the principal, from the CalDAV `calendar-home-set` property
+    pub calendar_home_set: Vec<Url>,
+    /// Collections containing the address books of the principal, from the CardDAV `addressbook-home-set`
+    /// property
+    pub addressbook_home_set: Vec<Url>,
+}
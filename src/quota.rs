@@ -0,0 +1,122 @@
+//! ## Quota
+//!
+//! Storage quota of remote collections
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::webdav_xml::properties::QuotaBytes;
+
+/// Quota of a collection, as returned by [`crate::WebDAVFs::quota`].
+///
+/// A property the server doesn't report is [`QuotaBytes::Unknown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    /// Space available to the user, from the `quota-available-bytes` property
+    pub available: QuotaBytes,
+    /// Space used by the collection, from the `quota-used-bytes` property
+    pub used: QuotaBytes,
+}
+
+impl Default for Quota {
+    fn default() -> Self {
+        Self {
+            available: QuotaBytes::Unknown,
+            used: QuotaBytes::Unknown,
+        }
+    }
+}
+
+impl Quota {
+    /// Returns whether `size` bytes can be written according to the available space; an unknown or unlimited
+    /// quota is assumed to be large enough.
+    pub fn fits(&self, size: u64) -> bool {
+        self.available
+            .bytes()
+            .is_none_or(|available| size <= available)
+    }
+}
+
+impl fmt::Display for Quota {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} used, {} available", self.used, self.available)
+    }
+}
+
+/// Error of a write refused by the server for insufficient storage (`507 Insufficient Storage`), as returned by
+/// [`crate::WebDAVFs::last_insufficient_storage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsufficientStorage {
+    /// Path of the file which could not be written
+    pub path: PathBuf,
+    /// Size of the content which could not be written
+    pub size: u64,
+    /// Quota of the parent collection right after the failure, if the server reported it
+    pub quota: Option<Quota>,
+}
+
+impl fmt::Display for InsufficientStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "insufficient storage: cannot write {} bytes to {}",
+            self.size,
+            self.path.display()
+        )?;
+        match &self.quota {
+            Some(quota) => write!(f, " ({quota})"),
+            None => f.write_str(" (quota unknown)"),
+        }
+    }
+}
+
+impl std::error::Error for InsufficientStorage {}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_tell_whether_size_fits_quota() {
+        let quota = Quota {
+            available: QuotaBytes::Bytes(1024),
+            used: QuotaBytes::Bytes(4096),
+        };
+        assert!(quota.fits(1024));
+        assert!(!quota.fits(1025));
+        assert!(Quota::default().fits(u64::MAX));
+        let unlimited = Quota {
+            available: QuotaBytes::Unlimited,
+            used: QuotaBytes::Bytes(4096),
+        };
+        assert!(unlimited.fits(u64::MAX));
+    }
+
+    #[test]
+    fn should_display_insufficient_storage() {
+        let error = InsufficientStorage {
+            path: PathBuf::from("/docs/video.mp4"),
+            size: 2048,
+            quota: Some(Quota {
+                available: QuotaBytes::Bytes(1024),
+                used: QuotaBytes::Bytes(4096),
+            }),
+        };
+        assert_eq!(
+            error.to_string(),
+            "insufficient storage: cannot write 2048 bytes to /docs/video.mp4 \
+             (4096 bytes used, 1024 bytes available)"
+        );
+        let error = InsufficientStorage {
+            quota: None,
+            ..error
+        };
+        assert_eq!(
+            error.to_string(),
+            "insufficient storage: cannot write 2048 bytes to /docs/video.mp4 (quota unknown)"
+        );
+    }
+}
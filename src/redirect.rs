@@ -0,0 +1,127 @@
+//! ## Redirect
+//!
+//! Policy for following redirects returned by the server
+
+use http::Method;
+use reqwest::blocking::Response;
+use reqwest::header::LOCATION;
+
+use crate::client::{method, Request};
+
+/// Policy used to follow redirects (`301`, `302`, `303`, `307` and `308`) returned by the server.
+///
+/// Redirects are followed only for the configured methods; the redirected request is sent with the
+/// same method, headers and body, except for `303 See Other`, which is always followed with a `GET`.
+/// Credentials are never sent to an origin other than the one of the server.
+///
+/// By default `GET`, `HEAD`, `OPTIONS`, `PROPFIND` and `PUT` requests are redirected, up to 10 hops.
+#[derive(Debug, Clone)]
+pub struct RedirectPolicy {
+    max_hops: usize,
+    follow: Vec<Method>,
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        Self {
+            max_hops: 10,
+            follow: vec![
+                Method::GET,
+                Method::HEAD,
+                Method::OPTIONS,
+                Method::PUT,
+                method("PROPFIND"),
+            ],
+        }
+    }
+}
+
+impl RedirectPolicy {
+    /// Policy which never follows redirects
+    pub fn none() -> Self {
+        Self::default().max_hops(0)
+    }
+
+    /// Set the maximum amount of redirects to follow for a single request
+    pub fn max_hops(mut self, hops: usize) -> Self {
+        self.max_hops = hops;
+        self
+    }
+
+    /// Set the methods for which redirects are followed
+    pub fn follow_methods(mut self, methods: impl IntoIterator<Item = Method>) -> Self {
+        self.follow = methods.into_iter().collect();
+        self
+    }
+
+    /// Returns whether redirects are followed for requests with `method`
+    pub fn follows(&self, method: &Method) -> bool {
+        self.max_hops > 0 && self.follow.contains(method)
+    }
+
+    /// Returns the maximum amount of redirects to follow for a single request
+    pub(crate) fn hops(&self) -> usize {
+        self.max_hops
+    }
+
+    /// Get the request to send to follow the redirect in `response`, if it must be followed
+    pub(crate) fn next(&self, request: &Request, response: &Response) -> Option<Request> {
+        if !self.follows(&request.method) {
+            return None;
+        }
+        let status = response.status().as_u16();
+        if !matches!(status, 301 | 302 | 303 | 307 | 308) {
+            return None;
+        }
+        let location = response.headers().get(LOCATION)?.to_str().ok()?;
+        let url = request.url.join(location).ok()?;
+
+        let mut next = request.clone();
+        next.url = url;
+        if status == 303 && request.method != Method::HEAD {
+            next.method = Method::GET;
+            next.body = None;
+            next.headers
+                .retain(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+        }
+
+        Some(next)
+    }
+
+    /// Returns whether the redirect with `status` is permanent
+    pub(crate) fn is_permanent(status: u16) -> bool {
+        matches!(status, 301 | 308)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_tell_whether_method_is_followed() {
+        let policy = RedirectPolicy::default();
+        assert!(policy.follows(&Method::GET));
+        assert!(policy.follows(&Method::PUT));
+        assert!(policy.follows(&method("PROPFIND")));
+        assert!(!policy.follows(&Method::DELETE));
+        assert!(!policy.follows(&method("MOVE")));
+
+        assert!(!RedirectPolicy::none().follows(&Method::GET));
+
+        let policy = policy.follow_methods([Method::DELETE]);
+        assert!(policy.follows(&Method::DELETE));
+        assert!(!policy.follows(&Method::GET));
+    }
+
+    #[test]
+    fn should_tell_whether_redirect_is_permanent() {
+        assert_eq!(RedirectPolicy::is_permanent(301), true);
+        assert_eq!(RedirectPolicy::is_permanent(308), true);
+        assert_eq!(RedirectPolicy::is_permanent(302), false);
+        assert_eq!(RedirectPolicy::is_permanent(307), false);
+    }
+}
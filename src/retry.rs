@@ -0,0 +1,247 @@
+//! ## Retry
+//!
+//! Retry policy for requests failing with a transient error
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime};
+
+use http::Method;
+use reqwest::blocking::Response;
+use reqwest::Error;
+
+use crate::client::method;
+
+/// Status codes which are considered transient
+const TRANSIENT_STATUS: &[u16] = &[408, 429, 502, 503, 504];
+/// Status codes for which the `Retry-After` header is honoured
+const RETRY_AFTER_STATUS: &[u16] = &[429, 503];
+
+/// Policy used to retry requests which failed with a transient error.
+///
+/// A request is retried only if its method is listed as idempotent, and either the transport failed
+/// (the connection could not be established or timed out) or the server answered with `408`, `429`, `502`, `503` or `504`.
+///
+/// Between two attempts the client waits for an exponential backoff with jitter; if the server answered
+/// `429` or `503` with a `Retry-After` header, that delay is used instead, capped to `max_backoff`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    idempotent: Vec<Method>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+            idempotent: vec![
+                Method::GET,
+                Method::HEAD,
+                Method::OPTIONS,
+                Method::PUT,
+                Method::DELETE,
+                method("PROPFIND"),
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy which never retries requests
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Set the maximum amount of attempts for a request, including the first one.
+    pub fn max_attempts(mut self, attempts: usize) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set the backoff to wait before the first retry. The backoff is doubled on each following retry.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the maximum time to wait between two attempts
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Enable or disable random jitter on the backoff
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the methods which are considered idempotent and so can be retried.
+    pub fn idempotent_methods(mut self, methods: impl IntoIterator<Item = Method>) -> Self {
+        self.idempotent = methods.into_iter().collect();
+        self
+    }
+
+    /// Returns whether requests with `method` may be retried
+    pub fn is_idempotent(&self, method: &Method) -> bool {
+        self.idempotent.contains(method)
+    }
+
+    /// Run `request` until it succeeds, fails with a non transient error or attempts are exhausted.
+    pub(crate) fn run<F>(&self, method: &Method, request: F) -> Result<Response, Error>
+    where
+        F: Fn() -> Result<Response, Error>,
+    {
+        let max_attempts = if self.is_idempotent(method) {
+            self.max_attempts
+        } else {
+            1
+        };
+
+        let mut attempt = 1;
+        loop {
+            let result = request();
+            if attempt >= max_attempts {
+                return result;
+            }
+            let delay = match &result {
+                Err(err) if err.is_connect() || err.is_timeout() => {
+                    debug!("{method} request failed: {err}");
+                    self.backoff(attempt)
+                }
+                Ok(response) if TRANSIENT_STATUS.contains(&response.status().as_u16()) => {
+                    debug!("{method} request failed with status {}", response.status());
+                    Self::retry_after(response)
+                        .map(|delay| delay.min(self.max_backoff))
+                        .unwrap_or_else(|| self.backoff(attempt))
+                }
+                _ => return result,
+            };
+            debug!(
+                "retrying {method} request in {}ms (attempt {}/{max_attempts})",
+                delay.as_millis(),
+                attempt + 1
+            );
+            std::thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    /// Get the backoff to wait after the `attempt`-th attempt failed
+    fn backoff(&self, attempt: usize) -> Duration {
+        let exp = (attempt.saturating_sub(1)).min(31) as u32;
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(exp))
+            .min(self.max_backoff);
+        if self.jitter {
+            // equal jitter: keep half of the backoff and randomize the other half
+            let half = backoff / 2;
+            half + half.mul_f64(Self::random())
+        } else {
+            backoff
+        }
+    }
+
+    /// Get the delay requested by the server through the `Retry-After` header
+    fn retry_after(response: &Response) -> Option<Duration> {
+        if !RETRY_AFTER_STATUS.contains(&response.status().as_u16()) {
+            return None;
+        }
+        let value = response.headers().get("retry-after")?.to_str().ok()?;
+        parse_retry_after(value, SystemTime::now())
+    }
+
+    /// Get a pseudo-random number in range `[0, 1)`
+    fn random() -> f64 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos(),
+        );
+        (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Parse the value of a `Retry-After` header, which is either an amount of seconds or an HTTP-date.
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(now).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_compute_exponential_backoff() {
+        let policy = RetryPolicy::default()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(1000))
+            .jitter(false);
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(5), Duration::from_millis(1000));
+        assert_eq!(policy.backoff(100), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn should_apply_jitter_to_backoff() {
+        let policy = RetryPolicy::default()
+            .initial_backoff(Duration::from_millis(100))
+            .jitter(true);
+        for _ in 0..32 {
+            let backoff = policy.backoff(2);
+            assert!(backoff >= Duration::from_millis(100));
+            assert!(backoff <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn should_tell_whether_method_is_idempotent() {
+        let policy = RetryPolicy::default();
+        assert!(policy.is_idempotent(&Method::GET));
+        assert!(policy.is_idempotent(&method("PROPFIND")));
+        assert!(!policy.is_idempotent(&Method::POST));
+        assert!(!policy.is_idempotent(&method("MOVE")));
+        assert!(!policy.is_idempotent(&method("MKCOL")));
+
+        let policy = policy.idempotent_methods([method("MOVE")]);
+        assert!(!policy.is_idempotent(&Method::GET));
+        assert!(policy.is_idempotent(&method("MOVE")));
+    }
+
+    #[test]
+    fn should_parse_retry_after() {
+        let now = httpdate::parse_http_date("Sat, 02 Mar 2024 15:44:46 GMT").unwrap();
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Sat, 02 Mar 2024 15:45:16 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Sat, 02 Mar 2024 15:40:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
@@ -0,0 +1,362 @@
+//! ## Search
+//!
+//! DASL search queries (RFC 5323)
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::webdav_xml::elements::{
+    BasicSearch, Depth, Href, Limit, Order, Properties, Scope, SearchCondition,
+};
+use crate::webdav_xml::properties::{ContentLength, ContentType, DisplayName, LastModified};
+use crate::webdav_xml::Value;
+
+/// A property which search results can be filtered and ordered by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchProperty {
+    /// The size of files (`getcontentlength`)
+    Size,
+    /// The last modification time (`getlastmodified`)
+    LastModified,
+    /// The content type of files (`getcontenttype`)
+    ContentType,
+    /// The display name (`displayname`)
+    DisplayName,
+    /// Any other property, e.g. a server specific one
+    Named {
+        namespace: String,
+        prefix: String,
+        name: String,
+    },
+}
+
+impl SearchProperty {
+    /// Build the `prop` element naming the property
+    fn prop(&self) -> Properties {
+        match self {
+            Self::Size => Properties::new().with::<ContentLength>(),
+            Self::LastModified => Properties::new().with::<LastModified>(),
+            Self::ContentType => Properties::new().with::<ContentType>(),
+            Self::DisplayName => Properties::new().with::<DisplayName>(),
+            Self::Named {
+                namespace,
+                prefix,
+                name,
+            } => Properties::new().with_name(namespace, prefix, name),
+        }
+    }
+}
+
+/// Comparison operators of a [`Filter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    Eq,
+}
+
+/// Condition on the resources matched by a [`SearchQuery`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter(SearchCondition);
+
+impl Filter {
+    /// Compare `property` with `literal`, which must be formatted as the server reports the property
+    pub fn compare(property: SearchProperty, op: Comparison, literal: impl Into<String>) -> Self {
+        let (prop, literal) = (property.prop(), literal.into());
+        Self(match op {
+            Comparison::Lt => SearchCondition::Lt(prop, literal),
+            Comparison::Lte => SearchCondition::Lte(prop, literal),
+            Comparison::Gt => SearchCondition::Gt(prop, literal),
+            Comparison::Gte => SearchCondition::Gte(prop, literal),
+            Comparison::Eq => SearchCondition::Eq(prop, literal),
+        })
+    }
+
+    /// Match `property` against `pattern`, where `%` matches any sequence of characters and `_` any single
+    /// character
+    pub fn like(property: SearchProperty, pattern: impl Into<String>) -> Self {
+        Self(SearchCondition::Like(property.prop(), pattern.into()))
+    }
+
+    /// Files larger than `size` bytes
+    pub fn size_gt(size: u64) -> Self {
+        Self::compare(SearchProperty::Size, Comparison::Gt, size.to_string())
+    }
+
+    /// Files smaller than `size` bytes
+    pub fn size_lt(size: u64) -> Self {
+        Self::compare(SearchProperty::Size, Comparison::Lt, size.to_string())
+    }
+
+    /// Resources modified after `time`
+    pub fn modified_after(time: SystemTime) -> Self {
+        Self::compare(
+            SearchProperty::LastModified,
+            Comparison::Gt,
+            http_date(time),
+        )
+    }
+
+    /// Resources modified before `time`
+    pub fn modified_before(time: SystemTime) -> Self {
+        Self::compare(
+            SearchProperty::LastModified,
+            Comparison::Lt,
+            http_date(time),
+        )
+    }
+
+    /// Files with the content type `mime`, e.g. `text/plain`
+    pub fn content_type(mime: &str) -> Self {
+        Self::compare(SearchProperty::ContentType, Comparison::Eq, mime)
+    }
+
+    /// Resources whose display name matches `pattern`, as [`Filter::like`] does
+    pub fn displayname_like(pattern: &str) -> Self {
+        Self::like(SearchProperty::DisplayName, pattern)
+    }
+
+    /// Collections
+    pub fn is_dir() -> Self {
+        Self(SearchCondition::IsCollection)
+    }
+
+    /// Resources on which `property` is defined
+    pub fn is_defined(property: SearchProperty) -> Self {
+        Self(SearchCondition::IsDefined(property.prop()))
+    }
+
+    /// Resources matching both this filter and `other`
+    pub fn and(self, other: Filter) -> Self {
+        match self.0 {
+            SearchCondition::And(mut conditions) => {
+                conditions.push(other.0);
+                Self(SearchCondition::And(conditions))
+            }
+            condition => Self(SearchCondition::And(vec![condition, other.0])),
+        }
+    }
+
+    /// Resources matching either this filter or `other`
+    pub fn or(self, other: Filter) -> Self {
+        match self.0 {
+            SearchCondition::Or(mut conditions) => {
+                conditions.push(other.0);
+                Self(SearchCondition::Or(conditions))
+            }
+            condition => Self(SearchCondition::Or(vec![condition, other.0])),
+        }
+    }
+
+    /// Resources not matching this filter
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        Self(SearchCondition::Not(Box::new(self.0)))
+    }
+
+    /// Get the search condition of the filter
+    pub fn condition(&self) -> &SearchCondition {
+        &self.0
+    }
+}
+
+impl From<Filter> for SearchCondition {
+    fn from(Filter(condition): Filter) -> Self {
+        condition
+    }
+}
+
+/// A DASL search, as run by [`crate::WebDAVFs::search`].
+///
+/// By default the whole tree under the scope is searched and all the matching resources are returned in the
+/// order chosen by the server.
+///
+/// ```
+/// use std::path::Path;
+///
+/// use remotefs_webdav::{Filter, SearchProperty, SearchQuery};
+///
+/// let query = SearchQuery::new(Path::new("/docs/"))
+///     .filter(Filter::size_gt(1024 * 1024))
+///     .filter(Filter::content_type("application/pdf").or(Filter::displayname_like("%.pdf")))
+///     .order_by(SearchProperty::Size, true)
+///     .limit(10);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    scope: PathBuf,
+    depth: Depth,
+    filter: Option<Filter>,
+    order: Vec<(SearchProperty, bool)>,
+    limit: Option<u64>,
+}
+
+impl SearchQuery {
+    /// Search the collection at `scope`
+    pub fn new(scope: &Path) -> Self {
+        Self {
+            scope: scope.to_path_buf(),
+            depth: Depth::Infinity,
+            filter: None,
+            order: Vec::new(),
+            limit: None,
+        }
+    }
+
+    /// Set how deep under the scope resources are searched
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Only return the resources matching `filter`; filters added more than once must all match
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = Some(match self.filter.take() {
+            Some(current) => current.and(filter),
+            None => filter,
+        });
+        self
+    }
+
+    /// Order the results by `property`, after the previously given properties
+    pub fn order_by(mut self, property: SearchProperty, descending: bool) -> Self {
+        self.order.push((property, descending));
+        self
+    }
+
+    /// Return at most `limit` results
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Get the path of the searched collection
+    pub fn scope(&self) -> &Path {
+        &self.scope
+    }
+
+    /// Build the `basicsearch` element of the query, searching the collection at `href` and selecting the
+    /// properties in `select`
+    pub(crate) fn basicsearch(self, href: Href, select: Properties) -> BasicSearch {
+        BasicSearch {
+            select,
+            from: vec![Scope {
+                href,
+                depth: self.depth,
+            }],
+            condition: self.filter.map(SearchCondition::from),
+            orderby: self
+                .order
+                .into_iter()
+                .map(|(property, descending)| Order {
+                    prop: property.prop(),
+                    descending,
+                })
+                .collect(),
+            limit: self.limit.map(Limit),
+        }
+    }
+}
+
+/// Translate the wildcards of `glob` (`*` and `?`) into a `like` pattern, escaping the characters which are
+/// special in `like` patterns
+#[cfg(feature = "find")]
+pub(crate) fn like_pattern(glob: &str) -> String {
+    let mut pattern = String::with_capacity(glob.len());
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push('%'),
+            '?' => pattern.push('_'),
+            '%' | '_' | '\\' => {
+                pattern.push('\\');
+                pattern.push(c);
+            }
+            c => pattern.push(c),
+        }
+    }
+    pattern
+}
+
+/// Format `time` as the `getlastmodified` property
+fn http_date(time: SystemTime) -> String {
+    match Value::from(LastModified(time.into())) {
+        Value::Text(date) => date.to_string(),
+        _ => unreachable!("getlastmodified is text"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::webdav_xml::elements::SearchRequest;
+    use crate::webdav_xml::IntoXml;
+
+    fn xml(query: SearchQuery) -> String {
+        let search = query.basicsearch(
+            Href(http::Uri::from_static("http://localhost/docs/")),
+            Properties::new().with::<ContentLength>(),
+        );
+        let body = SearchRequest(search).into_xml().unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn should_serialise_search_query() {
+        let body = xml(SearchQuery::new(Path::new("/docs/"))
+            .filter(Filter::size_gt(1024))
+            .filter(Filter::displayname_like("%.pdf").not())
+            .order_by(SearchProperty::LastModified, true)
+            .limit(10));
+        let body: String = body.split_whitespace().collect();
+        assert!(body.contains("<d:searchrequestxmlns:d=\"DAV:\"><d:basicsearch>"));
+        assert!(body.contains("<d:select><d:prop><d:getcontentlength/></d:prop></d:select>"));
+        assert!(body.contains(
+            "<d:from><d:scope><d:href>http://localhost/docs/</d:href><d:depth>infinity</d:depth>\
+             </d:scope></d:from>"
+        ));
+        assert!(body.contains(
+            "<d:where><d:and><d:gt><d:prop><d:getcontentlength/></d:prop><d:literal>1024</d:literal>\
+             </d:gt><d:not><d:like><d:prop><d:displayname/></d:prop><d:literal>%.pdf</d:literal>\
+             </d:like></d:not></d:and></d:where>"
+        ));
+        assert!(body.contains(
+            "<d:orderby><d:order><d:prop><d:getlastmodified/></d:prop><d:descending/></d:order>\
+             </d:orderby>"
+        ));
+        assert!(body.contains("<d:limit><d:nresults>10</d:nresults></d:limit>"));
+    }
+
+    #[test]
+    fn should_serialise_query_without_clauses() {
+        let body = xml(SearchQuery::new(Path::new("/docs/")).depth(Depth::One));
+        assert!(body.contains("<d:depth>1</d:depth>"));
+        assert!(!body.contains("where"));
+        assert!(!body.contains("orderby"));
+        assert!(!body.contains("limit"));
+    }
+
+    #[test]
+    #[cfg(feature = "find")]
+    fn should_translate_glob_into_like_pattern() {
+        assert_eq!(like_pattern("*.md"), "%.md");
+        assert_eq!(like_pattern("report-202?.odt"), "report-202_.odt");
+        assert_eq!(like_pattern("100%_done\\*"), "100\\%\\_done\\\\%");
+    }
+
+    #[test]
+    fn should_format_modification_time_as_http_date() {
+        let time = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1709394286);
+        assert_eq!(
+            Filter::modified_after(time).condition(),
+            &SearchCondition::Gt(
+                Properties::new().with::<LastModified>(),
+                String::from("Sat, 02 Mar 2024 15:44:46 GMT")
+            )
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! ## Sync
+//!
+//! Changes of collections reported by the `sync-collection` report (RFC 6578)
+
+use std::path::PathBuf;
+
+use crate::parser::{Entry, EntryError};
+
+/// Outcome of [`crate::WebDAVFs::sync_collection`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncResult {
+    /// Changes since the given token
+    Changes(SyncChanges),
+    /// The server doesn't know the given token anymore (`valid-sync-token` precondition), e.g. since it
+    /// expired: the collection must be listed again from scratch, then synchronized without token.
+    ResyncRequired,
+}
+
+/// Changes of a collection since a sync token
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncChanges {
+    /// Members added or changed since the token
+    pub entries: Vec<Entry>,
+    /// Paths of the members removed since the token, from the href of the responses with a `404` status
+    pub removed: Vec<PathBuf>,
+    /// Members which the server reported with another unsuccessful status
+    pub errors: Vec<EntryError>,
+    /// Token to pass to the next synchronization
+    pub token: String,
+    /// Whether the server returned only part of the changes, e.g. because of the limit set with
+    /// [`crate::WebDAVFs::sync_limit`]; the remaining ones are reported by the next synchronization
+    pub truncated: bool,
+}
@@ -0,0 +1,207 @@
+//! ## Tree
+//!
+//! Directory trees built from recursive listings
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::parser::{Entry, EntryError, Listing};
+
+/// A directory tree, as returned by [`crate::WebDAVFs::list_tree`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tree {
+    /// The entry at the root of the tree
+    pub entry: Entry,
+    /// Subtrees of the children of the entry; empty for files
+    pub children: Vec<Tree>,
+    /// Children of the entry which the server reported with an error status, e.g. unreadable directories
+    pub errors: Vec<EntryError>,
+}
+
+impl Tree {
+    /// Iterate over the entries of the tree in depth-first order, starting with the root
+    pub fn iter(&self) -> impl Iterator<Item = &Entry> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let tree = stack.pop()?;
+            stack.extend(tree.children.iter().rev());
+            Some(&tree.entry)
+        })
+    }
+
+    /// Iterate over the errors reported anywhere in the tree
+    pub fn errors(&self) -> impl Iterator<Item = &EntryError> {
+        let mut stack = vec![self];
+        let mut errors = [].iter();
+        std::iter::from_fn(move || loop {
+            if let Some(error) = errors.next() {
+                return Some(error);
+            }
+            let tree = stack.pop()?;
+            stack.extend(tree.children.iter().rev());
+            errors = tree.errors.iter();
+        })
+    }
+
+    /// Build the tree of the flat `listing` of a recursive listing.
+    ///
+    /// The root is the entry with the shortest path; entries whose parent is not part of the listing are
+    /// dropped. Returns `None` if the listing has no entries.
+    pub(crate) fn from_listing(listing: Listing) -> Option<Self> {
+        let mut entries = listing.entries;
+        let root = (0..entries.len()).min_by_key(|i| entries[*i].file.path.components().count())?;
+        let root = entries.remove(root);
+
+        let mut children: HashMap<PathBuf, Vec<Entry>> = HashMap::new();
+        for entry in entries {
+            match entry.file.path.parent() {
+                Some(parent) => children
+                    .entry(parent.to_path_buf())
+                    .or_default()
+                    .push(entry),
+                None => debug!(
+                    "dropping entry {} without parent",
+                    entry.file.path.display()
+                ),
+            }
+        }
+        let mut errors: HashMap<PathBuf, Vec<EntryError>> = HashMap::new();
+        for error in listing.errors {
+            match error.path.parent() {
+                Some(parent) => errors.entry(parent.to_path_buf()).or_default().push(error),
+                None => debug!("dropping error of {} without parent", error.path.display()),
+            }
+        }
+
+        let tree = Self::build(root, &mut children, &mut errors);
+        for path in children.keys().chain(errors.keys()) {
+            debug!(
+                "dropping children of {} which is not in the tree",
+                path.display()
+            );
+        }
+
+        Some(tree)
+    }
+
+    fn build(
+        entry: Entry,
+        children: &mut HashMap<PathBuf, Vec<Entry>>,
+        errors: &mut HashMap<PathBuf, Vec<EntryError>>,
+    ) -> Self {
+        let path: &Path = &entry.file.path;
+        let (subtrees, errors_of) = if entry.file.is_dir() {
+            (
+                children.remove(path).unwrap_or_default(),
+                errors.remove(path).unwrap_or_default(),
+            )
+        } else {
+            (Vec::new(), Vec::new())
+        };
+
+        Self {
+            children: subtrees
+                .into_iter()
+                .map(|child| Self::build(child, children, errors))
+                .collect(),
+            errors: errors_of,
+            entry,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+    use remotefs::fs::{FileType, Metadata};
+    use remotefs::{File, RemoteError, RemoteErrorType};
+
+    use super::*;
+
+    fn entry(path: &str) -> Entry {
+        Entry {
+            file: File {
+                path: PathBuf::from(path),
+                metadata: Metadata {
+                    file_type: if path.ends_with('/') {
+                        FileType::Directory
+                    } else {
+                        FileType::File
+                    },
+                    ..Metadata::default()
+                },
+            },
+            owner: None,
+            group: None,
+            locks: Vec::new(),
+            supported_locks: Vec::new(),
+            privileges: None,
+        }
+    }
+
+    fn paths(tree: &Tree) -> Vec<String> {
+        tree.iter()
+            .map(|entry| entry.file.path.display().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn should_build_tree_from_flat_listing() {
+        let listing = Listing {
+            entries: vec![
+                entry("/docs/"),
+                entry("/docs/2024/"),
+                entry("/docs/readme.md"),
+                entry("/docs/2024/march/"),
+                entry("/docs/2024/march/report.odt"),
+                entry("/docs/2024/summary.odt"),
+            ],
+            errors: vec![EntryError {
+                path: PathBuf::from("/docs/2024/private/"),
+                error: RemoteError::new(RemoteErrorType::CouldNotOpenFile),
+            }],
+        };
+        let tree = Tree::from_listing(listing).unwrap();
+
+        assert_eq!(
+            paths(&tree),
+            vec![
+                "/docs/",
+                "/docs/2024/",
+                "/docs/2024/march/",
+                "/docs/2024/march/report.odt",
+                "/docs/2024/summary.odt",
+                "/docs/readme.md",
+            ]
+        );
+        assert_eq!(tree.children.len(), 2);
+        assert!(tree.errors.is_empty());
+        assert_eq!(tree.children[0].errors.len(), 1);
+        assert_eq!(
+            tree.errors().map(|error| &error.path).collect::<Vec<_>>(),
+            vec![&PathBuf::from("/docs/2024/private/")]
+        );
+    }
+
+    #[test]
+    fn should_use_shortest_path_as_root() {
+        let listing = Listing {
+            entries: vec![entry("/docs/readme.md"), entry("/docs/")],
+            errors: Vec::new(),
+        };
+        let tree = Tree::from_listing(listing).unwrap();
+        assert_eq!(paths(&tree), vec!["/docs/", "/docs/readme.md"]);
+    }
+
+    #[test]
+    fn should_drop_orphan_entries() {
+        let listing = Listing {
+            entries: vec![entry("/docs/"), entry("/other/readme.md")],
+            errors: Vec::new(),
+        };
+        let tree = Tree::from_listing(listing).unwrap();
+        assert_eq!(paths(&tree), vec!["/docs/"]);
+        assert_eq!(Tree::from_listing(Listing::default()), None);
+    }
+}
@@ -0,0 +1,219 @@
+//! ## Version
+//!
+//! Version history of resources under DeltaV version control (RFC 3253)
+
+use std::io::Write;
+use std::path::Path;
+use std::time::SystemTime;
+
+use remotefs::{RemoteError, RemoteErrorType, RemoteResult};
+use reqwest::Url;
+
+use crate::client::Request;
+use crate::webdav_xml::elements::{Depth, Properties, VersionTree};
+use crate::webdav_xml::properties::{CreatorDisplayName, LastModified, VersionName};
+use crate::webdav_xml::IntoXml;
+use crate::WebDAVFs;
+
+/// A version of a version-controlled resource, as returned by [`WebDAVFs::versions`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    /// Url of the version resource
+    pub url: Url,
+    /// Name of the version, from the `version-name` property, e.g. `1.3`
+    pub name: Option<String>,
+    /// Name of the creator of the version, from the `creator-displayname` property
+    pub creator: Option<String>,
+    /// Time the version has been created at, from the `getlastmodified` property
+    pub modified: Option<SystemTime>,
+}
+
+impl WebDAVFs {
+    /// List the versions of the version-controlled resource at `path` with the `version-tree` report.
+    ///
+    /// Versions are returned in the order chosen by the server. Fails with
+    /// [`RemoteErrorType::UnsupportedFeature`] if the server or the resource doesn't support versioning.
+    pub fn versions(&mut self, path: &Path) -> RemoteResult<Vec<Version>> {
+        let url = self.url(path, false)?;
+        debug!("Listing versions of {}", url);
+        let body = VersionTree {
+            prop: Properties::new()
+                .with::<VersionName>()
+                .with::<CreatorDisplayName>()
+                .with::<LastModified>(),
+        }
+        .into_xml()
+        .map_err(|e| RemoteError::new_ex(RemoteErrorType::ProtocolError, e))?;
+        self.send(Request::report(url, Depth::Zero, body))?
+            .versions()
+    }
+
+    /// Download the content of `version`, writing it to `dest` as [`remotefs::RemoteFs::open_file`] does.
+    pub fn open_version(
+        &mut self,
+        version: &Version,
+        dest: Box<dyn Write + Send>,
+    ) -> RemoteResult<u64> {
+        debug!("Opening version: {}", version.url);
+        self.download(version.url.clone(), dest)
+    }
+
+    /// Roll the resource at `path` back to `version`, copying the version over it.
+    ///
+    /// Servers which keep the history of the resource record the rollback as a new version.
+    pub fn restore_version(&mut self, version: &Version, path: &Path) -> RemoteResult<()> {
+        let url = self.url(path, false)?;
+        debug!("Restoring version {} to {}", version.url, url);
+        self.protected(path, |fs| {
+            fs.send(Request::copy(version.url.clone(), &url))?.status()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    const VERSION_TREE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+        <D:multistatus xmlns:D="DAV:">
+        <D:response>
+        <D:href>/his/23/ver/V1</D:href>
+        <D:propstat>
+        <D:prop>
+        <D:version-name>V1</D:version-name>
+        <D:creator-displayname>Fred</D:creator-displayname>
+        <D:getlastmodified>Sat, 02 Mar 2024 15:44:46 GMT</D:getlastmodified>
+        </D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/his/23/ver/V2</D:href>
+        <D:propstat>
+        <D:prop><D:version-name>V2</D:version-name></D:prop>
+        <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        <D:propstat>
+        <D:prop><D:creator-displayname/><D:getlastmodified/></D:prop>
+        <D:status>HTTP/1.1 404 Not Found</D:status>
+        </D:propstat>
+        </D:response>
+        <D:response>
+        <D:href>/his/23/ver/V3</D:href>
+        <D:status>HTTP/1.1 403 Forbidden</D:status>
+        </D:response>
+        </D:multistatus>"#;
+
+    #[test]
+    fn should_list_versions() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| MockResponse::new(207).body(VERSION_TREE));
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let versions = client.versions(Path::new("/docs/report.odt")).unwrap();
+
+        let url = |path: &str| Url::parse(&server.url()).unwrap().join(path).unwrap();
+        assert_eq!(
+            versions,
+            vec![
+                Version {
+                    url: url("/his/23/ver/V1"),
+                    name: Some(String::from("V1")),
+                    creator: Some(String::from("Fred")),
+                    modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1709394286)),
+                },
+                Version {
+                    url: url("/his/23/ver/V2"),
+                    name: Some(String::from("V2")),
+                    creator: None,
+                    modified: None,
+                },
+            ]
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "REPORT");
+        assert_eq!(requests[0].path, "/docs/report.odt");
+        assert_eq!(requests[0].header("depth"), Some("0"));
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains(":version-tree"));
+        for name in ["version-name", "creator-displayname", "getlastmodified"] {
+            assert!(body.contains(&format!(":{name}/>")), "{name} in {body}");
+        }
+    }
+
+    #[test]
+    fn should_fail_listing_versions_of_unversioned_resource() {
+        crate::mock::logger();
+        let server = MockServer::new(|_| {
+            MockResponse::new(403).header("Content-Type", "application/xml; charset=utf-8").body(
+                r#"<?xml version="1.0"?><D:error xmlns:D="DAV:"><D:supported-report/></D:error>"#,
+            )
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let err = client.versions(Path::new("/docs/notes.txt")).unwrap_err();
+        assert_eq!(err.kind, RemoteErrorType::UnsupportedFeature);
+    }
+
+    /// Writer collecting the written content
+    #[derive(Clone, Default)]
+    struct Sink(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn should_open_and_restore_version() {
+        crate::mock::logger();
+        let server = MockServer::new(|req| match req.method.as_str() {
+            "GET" => MockResponse::new(200).body("first draft"),
+            _ => MockResponse::new(204),
+        });
+        let mut client = WebDAVFs::new("user", "password", &server.url());
+        let version = Version {
+            url: Url::parse(&server.url())
+                .unwrap()
+                .join("/his/23/ver/V1")
+                .unwrap(),
+            name: Some(String::from("V1")),
+            creator: None,
+            modified: None,
+        };
+
+        let sink = Sink::default();
+        assert_eq!(
+            client
+                .open_version(&version, Box::new(sink.clone()))
+                .unwrap(),
+            11
+        );
+        assert_eq!(sink.0.lock().unwrap().as_slice(), b"first draft");
+        client
+            .restore_version(&version, Path::new("/docs/report.odt"))
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/his/23/ver/V1");
+        assert_eq!(requests[1].method, "COPY");
+        assert_eq!(requests[1].path, "/his/23/ver/V1");
+        assert_eq!(
+            requests[1].header("destination"),
+            Some(format!("{}/docs/report.odt", server.url()).as_str())
+        );
+        assert_eq!(requests[1].header("overwrite"), Some("T"));
+    }
+}
@@ -0,0 +1,226 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Lenient parsing of the dates used by the `creationdate` and `getlastmodified` properties.
+//!
+//! Servers don't always stick to the format mandated by
+//! [RFC 4918](http://webdav.org/specs/rfc4918.html#PROPERTY_creationdate) for each property, so both
+//! properties accept any of:
+//!
+//! - RFC 3339 / ISO 8601 date-times, with or without fractional seconds and UTC offset
+//!   (`2024-03-02T15:44:46Z`, `2024-03-02T15:44:46.123`, `2024-03-02 15:44:46+01:00`)
+//! - the HTTP-date formats of [RFC 7231](https://www.rfc-editor.org/rfc/rfc7231#section-7.1.1.1):
+//!   IMF-fixdate (`Sat, 02 Mar 2024 15:44:46 GMT`), RFC 850 (`Saturday, 02-Mar-24 15:44:46 GMT`) and
+//!   asctime (`Sat Mar  2 15:44:46 2024`)
+//! - RFC 1123 / RFC 2822 dates with a numeric zone (`Sat, 02 Mar 2024 15:44:46 +0000`)
+//!
+//! Dates without a zone are assumed to be in UTC.
+
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use super::Error;
+
+const MONTHS: [(&str, Month); 12] = [
+    ("january", Month::January),
+    ("february", Month::February),
+    ("march", Month::March),
+    ("april", Month::April),
+    ("may", Month::May),
+    ("june", Month::June),
+    ("july", Month::July),
+    ("august", Month::August),
+    ("september", Month::September),
+    ("october", Month::October),
+    ("november", Month::November),
+    ("december", Month::December),
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+/// Parse a date in any of the supported formats
+pub(crate) fn parse(value: &str) -> Result<OffsetDateTime, Error> {
+    let value = value.trim();
+    parse_iso8601(value)
+        .or_else(|| parse_http_date(value))
+        .ok_or_else(|| Error::other(format!("invalid date: {value:?}")))
+}
+
+/// Format `date` as an IMF-fixdate, e.g. `Sat, 02 Mar 2024 15:44:46 GMT`
+pub(crate) fn format_http_date(date: OffsetDateTime) -> String {
+    let date = date.to_offset(UtcOffset::UTC);
+    format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+        &date.weekday().to_string()[..3],
+        date.day(),
+        &date.month().to_string()[..3],
+        date.year(),
+        date.hour(),
+        date.minute(),
+        date.second()
+    )
+}
+
+/// Parse `YYYY-MM-DD[(T| )hh:mm[:ss[.fraction]]][zone]`
+fn parse_iso8601(value: &str) -> Option<OffsetDateTime> {
+    let (date, rest) = (value.get(..10)?, value.get(10..)?);
+    let mut parts = date.split('-');
+    let year = parse_number(parts.next()?, 4)?;
+    let month = Month::try_from(parse_number(parts.next()?, 2)? as u8).ok()?;
+    let day = parse_number(parts.next()?, 2)? as u8;
+    let date = Date::from_calendar_date(year as i32, month, day).ok()?;
+
+    let mut chars = rest.chars();
+    let (time, offset) = match chars.next() {
+        None => (Time::MIDNIGHT, UtcOffset::UTC),
+        Some('T' | 't' | ' ') => {
+            let rest = chars.as_str();
+            let zone = rest.find(['Z', 'z', '+', '-']).unwrap_or(rest.len());
+            let (time, zone) = (rest.get(..zone)?, rest.get(zone..)?);
+            (parse_time(time.trim())?, parse_offset(zone.trim())?)
+        }
+        Some(_) => return None,
+    };
+
+    Some(PrimitiveDateTime::new(date, time).assume_offset(offset))
+}
+
+/// Parse an HTTP-date, tolerating any order of its components as long as each one is unambiguous
+fn parse_http_date(value: &str) -> Option<OffsetDateTime> {
+    let mut day = None;
+    let mut month = None;
+    let mut year = None;
+    let mut time = None;
+    let mut offset = UtcOffset::UTC;
+
+    let tokens = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty());
+    for token in tokens {
+        if token.contains(':') {
+            time = Some(parse_time(token)?);
+        } else if token.starts_with(['+', '-']) || is_utc(token) {
+            offset = parse_offset(token)?;
+        } else if token.contains('-') {
+            // RFC 850 date, e.g. `02-Mar-24`
+            let mut parts = token.split('-');
+            day = Some(parse_number(parts.next()?, 2)? as u8);
+            month = Some(parse_month(parts.next()?)?);
+            year = Some(parse_year(parts.next()?)?);
+            if parts.next().is_some() {
+                return None;
+            }
+        } else if let Some(m) = parse_month(token) {
+            month = Some(m);
+        } else if is_weekday(token) {
+            continue;
+        } else if token.len() <= 2 && day.is_none() {
+            day = Some(parse_number(token, 2)? as u8);
+        } else {
+            year = Some(parse_year(token)?);
+        }
+    }
+
+    let date = Date::from_calendar_date(year?, month?, day?).ok()?;
+    Some(PrimitiveDateTime::new(date, time?).assume_offset(offset))
+}
+
+/// Parse `hh:mm[:ss[(.|,)fraction]]`
+fn parse_time(value: &str) -> Option<Time> {
+    let mut parts = value.split(':');
+    let hour = parse_number(parts.next()?, 2)? as u8;
+    let minute = parse_number(parts.next()?, 2)? as u8;
+    let (second, nanos) = match parts.next() {
+        None => (0, 0),
+        Some(seconds) => match seconds.split_once(['.', ',']) {
+            None => (parse_number(seconds, 2)? as u8, 0),
+            Some((seconds, fraction)) => {
+                if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                // keep nanoseconds precision and pad shorter fractions
+                let digits = &fraction[..fraction.len().min(9)];
+                let nanos = digits.parse::<u32>().ok()? * 10u32.pow(9 - digits.len() as u32);
+                (parse_number(seconds, 2)? as u8, nanos)
+            }
+        },
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Time::from_hms_nano(hour, minute, second, nanos).ok()
+}
+
+/// Parse an UTC offset: `Z`, `GMT`, `UTC`, `UT`, `±hh`, `±hhmm` or `±hh:mm`; an empty zone is UTC
+fn parse_offset(value: &str) -> Option<UtcOffset> {
+    if value.is_empty() || is_utc(value) {
+        return Some(UtcOffset::UTC);
+    }
+    let (sign, value) = match (value.strip_prefix('+'), value.strip_prefix('-')) {
+        (Some(value), _) => (1, value),
+        (_, Some(value)) => (-1, value),
+        _ => return None,
+    };
+    let (hours, minutes) = match value.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        // slice with `get`, since a 4 bytes value may not be ASCII
+        None if value.len() == 4 => (value.get(..2)?, value.get(2..)?),
+        None => (value, "00"),
+    };
+    let hours = parse_number(hours, 2)? as i8;
+    let minutes = parse_number(minutes, 2)? as i8;
+
+    UtcOffset::from_hms(sign * hours, sign * minutes, 0).ok()
+}
+
+/// Parse a year, interpreting two digits years as described by
+/// [RFC 7231](https://www.rfc-editor.org/rfc/rfc7231#section-7.1.1.1)
+fn parse_year(value: &str) -> Option<i32> {
+    let year = parse_number(value, 4)? as i32;
+    if value.len() > 2 {
+        return Some(year);
+    }
+    // a year more than 50 years in the future is the most recent year in the past with the same digits
+    let now = OffsetDateTime::now_utc().year();
+    let year = now - now % 100 + year;
+    Some(if year > now + 50 { year - 100 } else { year })
+}
+
+/// Parse a full or abbreviated month name
+fn parse_month(value: &str) -> Option<Month> {
+    let value = value.to_ascii_lowercase();
+    if value.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .find(|(name, _)| name.starts_with(&value))
+        .map(|(_, month)| *month)
+}
+
+/// Returns whether `value` is a full or abbreviated weekday name
+fn is_weekday(value: &str) -> bool {
+    let value = value.to_ascii_lowercase();
+    value.len() >= 3 && WEEKDAYS.iter().any(|name| name.starts_with(&value))
+}
+
+fn is_utc(value: &str) -> bool {
+    ["z", "gmt", "utc", "ut"].contains(&value.to_ascii_lowercase().as_str())
+}
+
+/// Parse an unsigned number of at most `max_digits` digits
+fn parse_number(value: &str, max_digits: usize) -> Option<u32> {
+    if value.is_empty() || value.len() > max_digits || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
@@ -0,0 +1,324 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use bytestring::ByteString;
+use nonempty::NonEmpty;
+
+use super::super::element::{ElementExt, ElementName};
+use super::super::elements::{Href, Privilege, Properties};
+use super::super::value::ValueMap;
+use super::super::{Element, Error, OptionExt, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `ace` XML element as defined in [RFC 3744](https://www.rfc-editor.org/rfc/rfc3744#section-5.5),
+/// granting or denying privileges to a principal.
+///
+/// With `invert` the privileges apply to every principal but the given one. Protected entries can't be changed
+/// and inherited ones come from the resource at `inherited`; both are ignored when setting an ACL.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ace {
+    pub principal: Principal,
+    pub invert: bool,
+    pub access: Access,
+    pub protected: bool,
+    pub inherited: Option<Href>,
+}
+
+impl Ace {
+    /// Grant `privileges` to `principal`
+    pub fn grant(principal: Principal, privileges: Vec<Privilege>) -> Self {
+        Self::new(principal, Access::Grant(privileges))
+    }
+
+    /// Deny `privileges` to `principal`
+    pub fn deny(principal: Principal, privileges: Vec<Privilege>) -> Self {
+        Self::new(principal, Access::Deny(privileges))
+    }
+
+    fn new(principal: Principal, access: Access) -> Self {
+        Self {
+            principal,
+            invert: false,
+            access,
+            protected: false,
+            inherited: None,
+        }
+    }
+}
+
+impl Element for Ace {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "ace";
+}
+
+impl TryFrom<&Value> for Ace {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let map = value.to_map()?;
+        let (principal, invert) = match map.get::<Invert>() {
+            Some(invert) => (invert?.0, true),
+            None => (map.get().required::<Principal>()??, false),
+        };
+        let access = match (map.get::<Grant>(), map.get::<Deny>()) {
+            (Some(grant), _) => Access::Grant(grant?.0),
+            (None, Some(deny)) => Access::Deny(deny?.0),
+            (None, None) => return Err(Error::MissingElement(Grant::LOCAL_NAME)),
+        };
+
+        Ok(Self {
+            principal,
+            invert,
+            access,
+            protected: map
+                .0
+                .contains_key(&Protected::element_name::<&'static str>()),
+            inherited: map
+                .get::<Inherited>()
+                .transpose()?
+                .map(|inherited| inherited.0),
+        })
+    }
+}
+
+impl From<Ace> for Value {
+    fn from(
+        Ace {
+            principal,
+            invert,
+            access,
+            protected,
+            inherited,
+        }: Ace,
+    ) -> Value {
+        let mut map = ValueMap::new();
+
+        if invert {
+            let mut invert = ValueMap::new();
+            invert.insert::<Principal>(principal.into());
+            map.insert::<Invert>(Value::Map(invert));
+        } else {
+            map.insert::<Principal>(principal.into());
+        }
+        match access {
+            Access::Grant(privileges) => map.insert::<Grant>(privileges_value(privileges)),
+            Access::Deny(privileges) => map.insert::<Deny>(privileges_value(privileges)),
+        }
+        if protected {
+            map.insert::<Protected>(Value::Empty);
+        }
+        if let Some(href) = inherited {
+            let mut inherited = ValueMap::new();
+            inherited.insert::<Href>(href.into());
+            map.insert::<Inherited>(Value::Map(inherited));
+        }
+
+        Value::Map(map)
+    }
+}
+
+/// Whether an [`Ace`] grants or denies its privileges
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Access {
+    Grant(Vec<Privilege>),
+    Deny(Vec<Privilege>),
+}
+
+impl Access {
+    /// The privileges granted or denied
+    pub fn privileges(&self) -> &[Privilege] {
+        match self {
+            Self::Grant(privileges) | Self::Deny(privileges) => privileges,
+        }
+    }
+}
+
+/// The `principal` XML element as defined in [RFC 3744](https://www.rfc-editor.org/rfc/rfc3744#section-5.5.1).
+///
+/// `Property` matches the principal in the given property of the resource, e.g. its `owner`. Principals
+/// defined by other specifications or by the server are kept as [`Principal::Other`], along with their
+/// content.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Principal {
+    Href(Href),
+    All,
+    Authenticated,
+    Unauthenticated,
+    Property(Properties),
+    /// The `self` principal, matching the resource itself when it is a principal
+    SelfPrincipal,
+    Other {
+        namespace: Option<ByteString>,
+        name: ByteString,
+        value: Value,
+    },
+}
+
+impl Element for Principal {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "principal";
+}
+
+impl TryFrom<&Value> for Principal {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let (name, value) = value
+            .to_map()?
+            .0
+            .first()
+            .ok_or(Error::MissingElement("principal"))?;
+        if name.namespace.as_deref() == Some(DAV_NAMESPACE) {
+            match &*name.local_name {
+                "href" => return Href::try_from(value).map(Self::Href),
+                "all" => return Ok(Self::All),
+                "authenticated" => return Ok(Self::Authenticated),
+                "unauthenticated" => return Ok(Self::Unauthenticated),
+                "property" => return Properties::try_from(value).map(Self::Property),
+                "self" => return Ok(Self::SelfPrincipal),
+                _ => {}
+            }
+        }
+
+        Ok(Self::Other {
+            namespace: name.namespace.clone(),
+            name: name.local_name.clone(),
+            value: value.clone(),
+        })
+    }
+}
+
+impl From<Principal> for Value {
+    fn from(principal: Principal) -> Value {
+        let (local_name, value) = match principal {
+            Principal::Href(href) => ("href", href.into()),
+            Principal::All => ("all", Value::Empty),
+            Principal::Authenticated => ("authenticated", Value::Empty),
+            Principal::Unauthenticated => ("unauthenticated", Value::Empty),
+            Principal::Property(prop) => ("property", prop.into()),
+            Principal::SelfPrincipal => ("self", Value::Empty),
+            Principal::Other {
+                namespace,
+                name,
+                value,
+            } => {
+                let mut map = ValueMap::new();
+                map.insert_raw(
+                    ElementName {
+                        namespace,
+                        prefix: None,
+                        local_name: name,
+                    },
+                    value,
+                );
+                return Value::Map(map);
+            }
+        };
+
+        let mut map = ValueMap::new();
+        map.insert_raw(
+            ElementName {
+                namespace: Some(DAV_NAMESPACE.into()),
+                prefix: Some(DAV_PREFIX.into()),
+                local_name: local_name.into(),
+            },
+            value,
+        );
+        Value::Map(map)
+    }
+}
+
+/// Write the `privilege` elements of a `grant` or `deny` element
+fn privileges_value(privileges: Vec<Privilege>) -> Value {
+    match NonEmpty::collect(privileges.into_iter().map(Value::from)) {
+        Some(privileges) => {
+            let mut map = ValueMap::new();
+            map.insert::<Privilege>(Value::List(Box::new(privileges)));
+            Value::Map(map)
+        }
+        None => Value::Empty,
+    }
+}
+
+/// The `invert` XML element, holding the principal which an [`Ace`] doesn't apply to
+struct Invert(Principal);
+
+impl Element for Invert {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "invert";
+}
+
+impl TryFrom<&Value> for Invert {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_map()?.get().required::<Principal>()?.map(Self)
+    }
+}
+
+/// The `grant` XML element
+struct Grant(Vec<Privilege>);
+
+impl Element for Grant {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "grant";
+}
+
+impl TryFrom<&Value> for Grant {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value
+            .to_map()?
+            .iter_all()
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// The `deny` XML element
+struct Deny(Vec<Privilege>);
+
+impl Element for Deny {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "deny";
+}
+
+impl TryFrom<&Value> for Deny {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        Grant::try_from(value).map(|Grant(privileges)| Self(privileges))
+    }
+}
+
+/// The `protected` XML element
+struct Protected;
+
+impl Element for Protected {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "protected";
+}
+
+/// The `inherited` XML element, holding the resource which an [`Ace`] is inherited from
+struct Inherited(Href);
+
+impl Element for Inherited {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "inherited";
+}
+
+impl TryFrom<&Value> for Inherited {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_map()?.get().required::<Href>()?.map(Self)
+    }
+}
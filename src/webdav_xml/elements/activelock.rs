@@ -0,0 +1,231 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use bytestring::ByteString;
+
+use super::super::elements::{Depth, Href, LockScope, LockType};
+use super::super::properties::Owner;
+use super::super::value::ValueMap;
+use super::super::{Element, Error, OptionExt, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `activelock` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_activelock).
+///
+/// The `owner` of a lock may contain any markup; owners which are neither a text nor an `href` are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActiveLock {
+    pub lockscope: LockScope,
+    pub locktype: LockType,
+    pub depth: Depth,
+    pub owner: Option<Owner>,
+    pub timeout: Option<Timeout>,
+    pub locktoken: Option<LockToken>,
+    pub lockroot: Option<LockRoot>,
+}
+
+impl Element for ActiveLock {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "activelock";
+}
+
+impl TryFrom<&Value> for ActiveLock {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let map = value.to_map()?;
+        Ok(Self {
+            lockscope: map.get().required::<LockScope>()??,
+            locktype: map.get().required::<LockType>()??,
+            depth: map.get().required::<Depth>()??,
+            owner: map.get_optional().flatten().and_then(Result::ok),
+            timeout: map.get_optional().flatten().transpose()?,
+            locktoken: map.get_optional().flatten().transpose()?,
+            lockroot: map.get_optional().flatten().transpose()?,
+        })
+    }
+}
+
+impl From<ActiveLock> for Value {
+    fn from(
+        ActiveLock {
+            lockscope,
+            locktype,
+            depth,
+            owner,
+            timeout,
+            locktoken,
+            lockroot,
+        }: ActiveLock,
+    ) -> Value {
+        let mut map = ValueMap::new();
+
+        map.insert::<LockScope>(lockscope.into());
+        map.insert::<LockType>(locktype.into());
+        map.insert::<Depth>(depth.into());
+        if let Some(owner) = owner {
+            map.insert::<Owner>(owner.into());
+        }
+        if let Some(timeout) = timeout {
+            map.insert::<Timeout>(timeout.into());
+        }
+        if let Some(locktoken) = locktoken {
+            map.insert::<LockToken>(locktoken.into());
+        }
+        if let Some(lockroot) = lockroot {
+            map.insert::<LockRoot>(lockroot.into());
+        }
+
+        Value::Map(map)
+    }
+}
+
+/// The `timeout` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_timeout).
+///
+/// The same values are used by the `Timeout` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Timeout {
+    Seconds(u64),
+    Infinite,
+}
+
+impl Timeout {
+    /// Get the timeout as a duration; `None` if the timeout is infinite
+    pub fn as_duration(&self) -> Option<Duration> {
+        match self {
+            Self::Seconds(seconds) => Some(Duration::from_secs(*seconds)),
+            Self::Infinite => None,
+        }
+    }
+}
+
+impl Element for Timeout {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "timeout";
+}
+
+impl FromStr for Timeout {
+    type Err = InvalidTimeout;
+
+    /// Parse a timeout; for a list of timeouts, as allowed by the `Timeout` header, the first valid one is used
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .find_map(|timeout| {
+                if timeout.eq_ignore_ascii_case("infinite") {
+                    return Some(Self::Infinite);
+                }
+                let (unit, seconds) = timeout.split_once('-')?;
+                if !unit.eq_ignore_ascii_case("second") {
+                    return None;
+                }
+                seconds.parse().ok().map(Self::Seconds)
+            })
+            .ok_or_else(|| InvalidTimeout(s.into()))
+    }
+}
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Seconds(seconds) => write!(f, "Second-{seconds}"),
+            Self::Infinite => f.write_str("Infinite"),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Timeout {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_str()?.parse().map_err(Error::other)
+    }
+}
+
+impl From<Timeout> for Value {
+    fn from(timeout: Timeout) -> Value {
+        timeout.to_string().into()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid timeout: {0}")]
+pub struct InvalidTimeout(String);
+
+/// The `locktoken` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_locktoken).
+///
+/// The token is kept as text, since tokens are usually URNs (e.g. `urn:uuid:...` or `opaquelocktoken:...`)
+/// which are not valid [`http::Uri`]s.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockToken(pub ByteString);
+
+impl Element for LockToken {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "locktoken";
+}
+
+impl TryFrom<&Value> for LockToken {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let token = value.to_map()?.get::<HrefText>().required::<HrefText>()??;
+        Ok(Self(token.0.trim().into()))
+    }
+}
+
+impl From<LockToken> for Value {
+    fn from(LockToken(token): LockToken) -> Value {
+        let mut map = ValueMap::new();
+        map.insert::<HrefText>(Value::Text(token));
+        Value::Map(map)
+    }
+}
+
+/// The `lockroot` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_lockroot).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockRoot(pub Href);
+
+impl Element for LockRoot {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "lockroot";
+}
+
+impl TryFrom<&Value> for LockRoot {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_map()?.get().required::<Href>()?.map(Self)
+    }
+}
+
+impl From<LockRoot> for Value {
+    fn from(LockRoot(href): LockRoot) -> Value {
+        let mut map = ValueMap::new();
+        map.insert::<Href>(href.into());
+        Value::Map(map)
+    }
+}
+
+/// An `href` element read as plain text
+struct HrefText(ByteString);
+
+impl Element for HrefText {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = Href::LOCAL_NAME;
+}
+
+impl TryFrom<&Value> for HrefText {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_str().cloned().map(Self)
+    }
+}
@@ -0,0 +1,71 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `depth` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_depth).
+///
+/// The same values are used by the `Depth` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Depth {
+    Zero,
+    One,
+    Infinity,
+}
+
+impl Depth {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Zero => "0",
+            Self::One => "1",
+            Self::Infinity => "infinity",
+        }
+    }
+}
+
+impl Element for Depth {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "depth";
+}
+
+impl FromStr for Depth {
+    type Err = InvalidDepth;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "0" => Ok(Self::Zero),
+            "1" => Ok(Self::One),
+            s if s.eq_ignore_ascii_case("infinity") => Ok(Self::Infinity),
+            s => Err(InvalidDepth(s.into())),
+        }
+    }
+}
+
+impl Display for Depth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<&Value> for Depth {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_str()?.parse().map_err(Error::other)
+    }
+}
+
+impl From<Depth> for Value {
+    fn from(depth: Depth) -> Value {
+        Value::Text(depth.as_str().into())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid depth: {0}")]
+pub struct InvalidDepth(String);
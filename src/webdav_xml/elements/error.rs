@@ -0,0 +1,281 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::fmt;
+
+use bytestring::ByteString;
+use nonempty::NonEmpty;
+
+use super::super::element::ElementName;
+use super::super::elements::Href;
+use super::super::value::ValueMap;
+use super::super::{Element, Error as XmlError, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The SabreDAV namespace, used for the `exception` and `message` elements
+const SABREDAV_NAMESPACE: &str = "http://sabredav.org/ns";
+/// The SabreDAV namespace prefix
+const SABREDAV_PREFIX: &str = "s";
+
+/// The `error` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_error).
+///
+/// Besides the precondition and postcondition codes, the `exception` and `message` elements sent by
+/// SabreDAV based servers are extracted too.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Error {
+    pub conditions: Vec<Condition>,
+    pub exception: Option<ByteString>,
+    pub message: Option<ByteString>,
+}
+
+/// A precondition or postcondition code reported in an [`Error`] element, as defined in
+/// [RFC 4918](http://webdav.org/specs/rfc4918.html#precondition.postcondition.xml.elements).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    LockTokenMatchesRequestUri,
+    LockTokenSubmitted(Vec<Href>),
+    NoConflictingLock(Vec<Href>),
+    NoExternalEntities,
+    PreservedLiveProperties,
+    PropfindFiniteDepth,
+    CannotModifyProtectedProperty,
+    /// The sync token of a `sync-collection` report is invalid or expired, as defined in
+    /// [RFC 6578](https://www.rfc-editor.org/rfc/rfc6578#section-3.2)
+    ValidSyncToken,
+    /// A `sync-collection` report has been truncated to the requested limit, as defined in
+    /// [RFC 6578](https://www.rfc-editor.org/rfc/rfc6578#section-3.6)
+    NumberOfMatchesWithinLimits,
+    /// The report is not supported by the resource, as defined in
+    /// [RFC 3253](https://www.rfc-editor.org/rfc/rfc3253#section-3.6)
+    SupportedReport,
+    /// The preconditions of the `ACL` method, as defined in
+    /// [RFC 3744](https://www.rfc-editor.org/rfc/rfc3744#section-8.1.1)
+    NoAceConflict,
+    NoProtectedAceConflict,
+    NoInheritedAceConflict,
+    LimitedNumberOfAces,
+    DenyBeforeGrant,
+    GrantOnly,
+    NoInvert,
+    NoAbstract,
+    NotSupportedPrivilege,
+    MissingRequiredPrincipal,
+    RecognizedPrincipal,
+    AllowedPrincipal,
+    /// A condition defined by another specification or by the server
+    Other {
+        namespace: Option<ByteString>,
+        name: ByteString,
+    },
+}
+
+impl Condition {
+    /// The local name of the condition element
+    pub fn name(&self) -> &str {
+        match self {
+            Self::LockTokenMatchesRequestUri => "lock-token-matches-request-uri",
+            Self::LockTokenSubmitted(_) => "lock-token-submitted",
+            Self::NoConflictingLock(_) => "no-conflicting-lock",
+            Self::NoExternalEntities => "no-external-entities",
+            Self::PreservedLiveProperties => "preserved-live-properties",
+            Self::PropfindFiniteDepth => "propfind-finite-depth",
+            Self::CannotModifyProtectedProperty => "cannot-modify-protected-property",
+            Self::ValidSyncToken => "valid-sync-token",
+            Self::NumberOfMatchesWithinLimits => "number-of-matches-within-limits",
+            Self::SupportedReport => "supported-report",
+            Self::NoAceConflict => "no-ace-conflict",
+            Self::NoProtectedAceConflict => "no-protected-ace-conflict",
+            Self::NoInheritedAceConflict => "no-inherited-ace-conflict",
+            Self::LimitedNumberOfAces => "limited-number-of-aces",
+            Self::DenyBeforeGrant => "deny-before-grant",
+            Self::GrantOnly => "grant-only",
+            Self::NoInvert => "no-invert",
+            Self::NoAbstract => "no-abstract",
+            Self::NotSupportedPrivilege => "not-supported-privilege",
+            Self::MissingRequiredPrincipal => "missing-required-principal",
+            Self::RecognizedPrincipal => "recognized-principal",
+            Self::AllowedPrincipal => "allowed-principal",
+            Self::Other { name, .. } => name,
+        }
+    }
+
+    fn from_element(name: &ElementName<ByteString>, value: &Value) -> Result<Self, XmlError> {
+        fn hrefs(value: &Value) -> Result<Vec<Href>, XmlError> {
+            match value {
+                Value::Map(map) => map.iter_all().collect(),
+                _ => Ok(Vec::new()),
+            }
+        }
+
+        if name.namespace.as_deref() != Some(DAV_NAMESPACE) {
+            return Ok(Self::Other {
+                namespace: name.namespace.clone(),
+                name: name.local_name.clone(),
+            });
+        }
+        Ok(match &*name.local_name {
+            "lock-token-matches-request-uri" => Self::LockTokenMatchesRequestUri,
+            "lock-token-submitted" => Self::LockTokenSubmitted(hrefs(value)?),
+            "no-conflicting-lock" => Self::NoConflictingLock(hrefs(value)?),
+            "no-external-entities" => Self::NoExternalEntities,
+            "preserved-live-properties" => Self::PreservedLiveProperties,
+            "propfind-finite-depth" => Self::PropfindFiniteDepth,
+            "cannot-modify-protected-property" => Self::CannotModifyProtectedProperty,
+            "valid-sync-token" => Self::ValidSyncToken,
+            "number-of-matches-within-limits" => Self::NumberOfMatchesWithinLimits,
+            "supported-report" => Self::SupportedReport,
+            "no-ace-conflict" => Self::NoAceConflict,
+            "no-protected-ace-conflict" => Self::NoProtectedAceConflict,
+            "no-inherited-ace-conflict" => Self::NoInheritedAceConflict,
+            "limited-number-of-aces" => Self::LimitedNumberOfAces,
+            "deny-before-grant" => Self::DenyBeforeGrant,
+            "grant-only" => Self::GrantOnly,
+            "no-invert" => Self::NoInvert,
+            "no-abstract" => Self::NoAbstract,
+            "not-supported-privilege" => Self::NotSupportedPrivilege,
+            "missing-required-principal" => Self::MissingRequiredPrincipal,
+            "recognized-principal" => Self::RecognizedPrincipal,
+            "allowed-principal" => Self::AllowedPrincipal,
+            _ => Self::Other {
+                namespace: name.namespace.clone(),
+                name: name.local_name.clone(),
+            },
+        })
+    }
+
+    fn into_element(self) -> (ElementName<ByteString>, Value) {
+        let value = match &self {
+            Self::LockTokenSubmitted(hrefs) | Self::NoConflictingLock(hrefs) => {
+                match NonEmpty::collect(hrefs.iter().cloned().map(Value::from)) {
+                    Some(hrefs) => {
+                        let mut map = ValueMap::new();
+                        map.insert::<Href>(Value::List(Box::new(hrefs)));
+                        Value::Map(map)
+                    }
+                    None => Value::Empty,
+                }
+            }
+            _ => Value::Empty,
+        };
+        let name = match self {
+            Self::Other { namespace, name } => ElementName {
+                namespace,
+                prefix: None,
+                local_name: name,
+            },
+            condition => ElementName {
+                namespace: Some(DAV_NAMESPACE.into()),
+                prefix: Some(DAV_PREFIX.into()),
+                local_name: condition.name().into(),
+            },
+        };
+
+        (name, value)
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())?;
+        match self {
+            Self::LockTokenSubmitted(hrefs) | Self::NoConflictingLock(hrefs)
+                if !hrefs.is_empty() =>
+            {
+                let hrefs: Vec<String> = hrefs.iter().map(|href| href.0.to_string()).collect();
+                write!(f, " ({})", hrefs.join(", "))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let conditions: Vec<String> = self.conditions.iter().map(|c| c.to_string()).collect();
+        f.write_str(&conditions.join(", "))?;
+        if let Some(exception) = &self.exception {
+            if !conditions.is_empty() {
+                f.write_str("; ")?;
+            }
+            f.write_str(exception)?;
+        }
+        if let Some(message) = &self.message {
+            if !conditions.is_empty() || self.exception.is_some() {
+                f.write_str(": ")?;
+            }
+            f.write_str(message)?;
+        }
+        Ok(())
+    }
+}
+
+impl Element for Error {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "error";
+}
+
+impl TryFrom<&Value> for Error {
+    type Error = XmlError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let map = match value {
+            Value::Map(map) => map,
+            // empty error element
+            _ => return Ok(Self::default()),
+        };
+
+        let mut error = Self::default();
+        for (name, value) in &map.0 {
+            if name.namespace.as_deref() == Some(SABREDAV_NAMESPACE) {
+                match &*name.local_name {
+                    "exception" => error.exception = Some(value.to_str()?.clone()),
+                    "message" => error.message = Some(value.to_str()?.clone()),
+                    _ => {}
+                }
+                continue;
+            }
+            match value {
+                Value::List(list) => {
+                    for value in list.iter() {
+                        error.conditions.push(Condition::from_element(name, value)?);
+                    }
+                }
+                value => error.conditions.push(Condition::from_element(name, value)?),
+            }
+        }
+
+        Ok(error)
+    }
+}
+
+impl From<Error> for Value {
+    fn from(
+        Error {
+            conditions,
+            exception,
+            message,
+        }: Error,
+    ) -> Value {
+        let mut map = ValueMap::new();
+
+        for condition in conditions {
+            let (name, value) = condition.into_element();
+            map.insert_raw(name, value);
+        }
+        for (name, value) in [("exception", exception), ("message", message)] {
+            if let Some(value) = value {
+                map.insert_raw(
+                    ElementName {
+                        namespace: Some(SABREDAV_NAMESPACE.into()),
+                        prefix: Some(SABREDAV_PREFIX.into()),
+                        local_name: name.into(),
+                    },
+                    Value::Text(value),
+                );
+            }
+        }
+
+        Value::Map(map)
+    }
+}
@@ -8,7 +8,7 @@ use super::super::value::Value;
 use super::super::{Element, Error, DAV_NAMESPACE, DAV_PREFIX};
 
 /// The `href` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_href).
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Href(pub http::Uri);
 
 impl Element for Href {
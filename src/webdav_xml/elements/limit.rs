@@ -0,0 +1,67 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::super::value::ValueMap;
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `limit` XML element as defined in [RFC 5323](https://www.rfc-editor.org/rfc/rfc5323#section-5.17),
+/// also used by the `sync-collection` report of [RFC 6578](https://www.rfc-editor.org/rfc/rfc6578#section-6.6).
+///
+/// Holds the maximum number of results of the `nresults` element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limit(pub u64);
+
+impl Element for Limit {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "limit";
+}
+
+impl TryFrom<&Value> for Limit {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value
+            .to_map()?
+            .get::<NResults>()
+            .ok_or(Error::MissingElement(NResults::LOCAL_NAME))?
+            .map(|NResults(nresults)| Self(nresults))
+    }
+}
+
+impl From<Limit> for Value {
+    fn from(Limit(nresults): Limit) -> Value {
+        let mut map = ValueMap::new();
+        map.insert::<NResults>(NResults(nresults).into());
+        Value::Map(map)
+    }
+}
+
+/// The `nresults` XML element
+struct NResults(u64);
+
+impl Element for NResults {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "nresults";
+}
+
+impl TryFrom<&Value> for NResults {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value
+            .to_str()?
+            .trim()
+            .parse()
+            .map(Self)
+            .map_err(Error::other)
+    }
+}
+
+impl From<NResults> for Value {
+    fn from(NResults(nresults): NResults) -> Value {
+        nresults.to_string().into()
+    }
+}
@@ -0,0 +1,179 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::super::value::ValueMap;
+use super::super::{Element, Error, OptionExt, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `lockentry` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_lockentry).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockEntry {
+    pub lockscope: LockScope,
+    pub locktype: LockType,
+}
+
+impl Element for LockEntry {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "lockentry";
+}
+
+impl TryFrom<&Value> for LockEntry {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let map = value.to_map()?;
+        Ok(Self {
+            lockscope: map.get().required::<LockScope>()??,
+            locktype: map.get().required::<LockType>()??,
+        })
+    }
+}
+
+impl From<LockEntry> for Value {
+    fn from(
+        LockEntry {
+            lockscope,
+            locktype,
+        }: LockEntry,
+    ) -> Value {
+        let mut map = ValueMap::new();
+
+        map.insert::<LockScope>(lockscope.into());
+        map.insert::<LockType>(locktype.into());
+
+        Value::Map(map)
+    }
+}
+
+/// The `lockscope` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_lockscope).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockScope {
+    Exclusive,
+    Shared,
+}
+
+impl Element for LockScope {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "lockscope";
+}
+
+impl TryFrom<&Value> for LockScope {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let map = value.to_map()?;
+
+        match (map.get::<Exclusive>(), map.get::<Shared>()) {
+            (Some(_), None) => Ok(Self::Exclusive),
+            (None, Some(_)) => Ok(Self::Shared),
+            (None, None) => Err(Error::MissingElement(Exclusive::LOCAL_NAME)),
+            (Some(_), Some(_)) => Err(Error::ConflictingElements(
+                "`exclusive` and `shared` must not be used at the same time",
+            )),
+        }
+    }
+}
+
+impl From<LockScope> for Value {
+    fn from(lockscope: LockScope) -> Value {
+        let mut map = ValueMap::new();
+
+        match lockscope {
+            LockScope::Exclusive => map.insert::<Exclusive>(Value::Empty),
+            LockScope::Shared => map.insert::<Shared>(Value::Empty),
+        }
+
+        Value::Map(map)
+    }
+}
+
+/// The `locktype` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_locktype).
+///
+/// RFC 4918 only defines `write` locks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockType {
+    Write,
+}
+
+impl Element for LockType {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "locktype";
+}
+
+impl TryFrom<&Value> for LockType {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value
+            .to_map()?
+            .get::<Write>()
+            .required::<Write>()?
+            .map(|_| Self::Write)
+    }
+}
+
+impl From<LockType> for Value {
+    fn from(locktype: LockType) -> Value {
+        let mut map = ValueMap::new();
+
+        match locktype {
+            LockType::Write => map.insert::<Write>(Value::Empty),
+        }
+
+        Value::Map(map)
+    }
+}
+
+/// The `exclusive` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_exclusive).
+struct Exclusive;
+
+impl Element for Exclusive {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "exclusive";
+}
+
+impl TryFrom<&Value> for Exclusive {
+    type Error = Error;
+
+    fn try_from(_: &Value) -> Result<Self, Self::Error> {
+        Ok(Exclusive)
+    }
+}
+
+/// The `shared` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_shared).
+struct Shared;
+
+impl Element for Shared {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "shared";
+}
+
+impl TryFrom<&Value> for Shared {
+    type Error = Error;
+
+    fn try_from(_: &Value) -> Result<Self, Self::Error> {
+        Ok(Shared)
+    }
+}
+
+/// The `write` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_write).
+struct Write;
+
+impl Element for Write {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "write";
+}
+
+impl TryFrom<&Value> for Write {
+    type Error = Error;
+
+    fn try_from(_: &Value) -> Result<Self, Self::Error> {
+        Ok(Write)
+    }
+}
@@ -5,19 +5,46 @@
 //! XML element definitions based on
 //! [RFC 4918](http://webdav.org/specs/rfc4918.html#xml.element.definitions).
 
+mod ace;
+mod activelock;
+mod depth;
+mod error;
 mod href;
+mod limit;
+mod lockentry;
+mod lockinfo;
 mod multistatus;
+mod privilege;
 mod prop;
+mod propertyupdate;
 mod propfind;
 mod propstat;
 mod response;
 mod responsedescription;
+mod searchrequest;
 mod status;
+mod synccollection;
+mod synctoken;
+mod versiontree;
 
+pub use self::ace::{Access, Ace, Principal};
+pub use self::activelock::{ActiveLock, LockRoot, LockToken, Timeout};
+pub use self::depth::Depth;
+pub use self::error::{Condition, Error};
 pub use self::href::Href;
+pub use self::limit::Limit;
+pub use self::lockentry::{LockEntry, LockScope, LockType};
+pub use self::lockinfo::LockInfo;
 pub use self::multistatus::Multistatus;
+pub use self::privilege::Privilege;
 pub use self::prop::Properties;
+pub use self::propertyupdate::{PropertyUpdate, Remove, Set};
+pub use self::propfind::{Include, Propfind};
 pub use self::propstat::Propstat;
 pub use self::response::Response;
 pub use self::responsedescription::ResponseDescription;
+pub use self::searchrequest::{BasicSearch, Order, Scope, SearchCondition, SearchRequest};
 pub use self::status::Status;
+pub use self::synccollection::{SyncCollection, SyncLevel};
+pub use self::synctoken::SyncToken;
+pub use self::versiontree::VersionTree;
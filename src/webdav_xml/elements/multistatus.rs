@@ -5,15 +5,19 @@
 use nonempty::NonEmpty;
 
 use super::super::elements::response::Response;
-use super::super::elements::ResponseDescription;
+use super::super::elements::{ResponseDescription, SyncToken};
 use super::super::value::ValueMap;
 use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
 
 /// The `multistatus` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_multistatus).
+///
+/// The `sync-token` element is added by the `sync-collection` report of
+/// [RFC 6578](https://www.rfc-editor.org/rfc/rfc6578#section-6.4).
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Multistatus {
     pub response: Vec<Response>,
     pub responsedescription: Option<ResponseDescription>,
+    pub sync_token: Option<SyncToken>,
 }
 
 impl Element for Multistatus {
@@ -55,6 +59,7 @@ impl TryFrom<&Value> for Multistatus {
         Ok(Multistatus {
             response,
             responsedescription: map.get().transpose()?,
+            sync_token: map.get().transpose()?,
         })
     }
 }
@@ -64,6 +69,7 @@ impl From<Multistatus> for Value {
         Multistatus {
             response,
             responsedescription,
+            sync_token,
         }: Multistatus,
     ) -> Value {
         let mut map = ValueMap::new();
@@ -77,6 +83,9 @@ impl From<Multistatus> for Value {
         if let Some(responsedescription) = responsedescription {
             map.insert::<ResponseDescription>(responsedescription.into())
         }
+        if let Some(sync_token) = sync_token {
+            map.insert::<SyncToken>(sync_token.into())
+        }
 
         Value::Map(map)
     }
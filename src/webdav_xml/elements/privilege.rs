@@ -0,0 +1,131 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::fmt;
+
+use bytestring::ByteString;
+
+use super::super::element::ElementName;
+use super::super::value::ValueMap;
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `privilege` XML element as defined in [RFC 3744](https://www.rfc-editor.org/rfc/rfc3744#section-5.4),
+/// holding a single privilege.
+///
+/// Aggregate privileges contain other ones, e.g. `write` contains `write-content`, `bind` and `unbind`, while
+/// `all` contains every privilege.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Privilege {
+    Read,
+    Write,
+    WriteProperties,
+    WriteContent,
+    Unlock,
+    ReadAcl,
+    ReadCurrentUserPrivilegeSet,
+    WriteAcl,
+    Bind,
+    Unbind,
+    All,
+    /// A privilege defined by another specification or by the server
+    Other {
+        namespace: Option<ByteString>,
+        name: ByteString,
+    },
+}
+
+impl Privilege {
+    /// The local name of the privilege element
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::WriteProperties => "write-properties",
+            Self::WriteContent => "write-content",
+            Self::Unlock => "unlock",
+            Self::ReadAcl => "read-acl",
+            Self::ReadCurrentUserPrivilegeSet => "read-current-user-privilege-set",
+            Self::WriteAcl => "write-acl",
+            Self::Bind => "bind",
+            Self::Unbind => "unbind",
+            Self::All => "all",
+            Self::Other { name, .. } => name,
+        }
+    }
+
+    fn from_element(name: &ElementName<ByteString>) -> Self {
+        if name.namespace.as_deref() != Some(DAV_NAMESPACE) {
+            return Self::Other {
+                namespace: name.namespace.clone(),
+                name: name.local_name.clone(),
+            };
+        }
+        match &*name.local_name {
+            "read" => Self::Read,
+            "write" => Self::Write,
+            "write-properties" => Self::WriteProperties,
+            "write-content" => Self::WriteContent,
+            "unlock" => Self::Unlock,
+            "read-acl" => Self::ReadAcl,
+            "read-current-user-privilege-set" => Self::ReadCurrentUserPrivilegeSet,
+            "write-acl" => Self::WriteAcl,
+            "bind" => Self::Bind,
+            "unbind" => Self::Unbind,
+            "all" => Self::All,
+            _ => Self::Other {
+                namespace: name.namespace.clone(),
+                name: name.local_name.clone(),
+            },
+        }
+    }
+
+    fn into_element(self) -> ElementName<ByteString> {
+        match self {
+            Self::Other { namespace, name } => ElementName {
+                namespace,
+                prefix: None,
+                local_name: name,
+            },
+            privilege => ElementName {
+                namespace: Some(DAV_NAMESPACE.into()),
+                prefix: Some(DAV_PREFIX.into()),
+                local_name: privilege.name().into(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Privilege {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Element for Privilege {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "privilege";
+}
+
+impl TryFrom<&Value> for Privilege {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value
+            .to_map()?
+            .0
+            .keys()
+            .next()
+            .map(Self::from_element)
+            .ok_or(Error::MissingElement("privilege"))
+    }
+}
+
+impl From<Privilege> for Value {
+    fn from(privilege: Privilege) -> Value {
+        let mut map = ValueMap::new();
+        map.insert_raw(privilege.into_element(), Value::Empty);
+        Value::Map(map)
+    }
+}
@@ -2,8 +2,11 @@
 //
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use super::super::element::Element;
-use super::super::properties::{ContentLength, CreationDate, LastModified};
+use super::super::element::{Element, ElementName};
+use super::super::properties::{
+    Acl, ContentLength, CreationDate, CurrentUserPrivilegeSet, Executable, Group, LastModified,
+    LockDiscovery, Owner, ResourceType, SupportedLock,
+};
 use super::super::value::{Value, ValueMap};
 use super::super::{Error, DAV_NAMESPACE, DAV_PREFIX};
 
@@ -15,6 +18,89 @@ use super::super::{Error, DAV_NAMESPACE, DAV_PREFIX};
 pub struct Properties(ValueMap);
 
 impl Properties {
+    /// Create an empty `prop` element
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the property `P` without a value, e.g. to request it in a `propfind` element.
+    pub fn with<P: Element>(mut self) -> Self {
+        self.0.insert::<P>(Value::Empty);
+        self
+    }
+
+    /// Add the property `local_name` of `namespace` without a value, e.g. to request a server specific
+    /// property in a `propfind` element. `prefix` is used for the namespace when serialised.
+    pub fn with_name(mut self, namespace: &str, prefix: &str, local_name: &str) -> Self {
+        self.0.insert_raw(
+            ElementName {
+                namespace: Some(namespace.into()),
+                prefix: Some(prefix.into()),
+                local_name: local_name.into(),
+            },
+            Value::Empty,
+        );
+        self
+    }
+
+    /// Add the property `P` along with its value, e.g. to set it in a `propertyupdate` element.
+    pub fn with_value<P: Element + Into<Value>>(mut self, property: P) -> Self {
+        self.0.insert::<P>(property.into());
+        self
+    }
+
+    /// Add the property `local_name` of `namespace` along with its value, e.g. to set a dead property in a
+    /// `propertyupdate` element. `prefix` is used for the namespace when serialised.
+    pub fn with_name_value(
+        mut self,
+        namespace: &str,
+        prefix: &str,
+        local_name: &str,
+        value: impl Into<Value>,
+    ) -> Self {
+        self.0.insert_raw(
+            ElementName {
+                namespace: Some(namespace.into()),
+                prefix: Some(prefix.into()),
+                local_name: local_name.into(),
+            },
+            value.into(),
+        );
+        self
+    }
+
+    /// Add the properties of `other` which are not already present, e.g. to request additional properties.
+    pub fn with_properties(mut self, Properties(other): Properties) -> Self {
+        for (name, value) in other.0 {
+            if !self.0 .0.contains_key(&name) {
+                self.0.insert_raw(name, value);
+            }
+        }
+        self
+    }
+
+    /// Returns whether the element contains no property
+    pub fn is_empty(&self) -> bool {
+        self.0 .0.is_empty()
+    }
+
+    /// Get the raw value of the property `local_name` of `namespace`, e.g. a server specific property.
+    pub fn value(&self, namespace: &str, local_name: &str) -> Option<&Value> {
+        self.0 .0.get(&ElementName {
+            namespace: Some(namespace),
+            prefix: None,
+            local_name,
+        })
+    }
+
+    /// Iterate over the names of the properties as `(namespace, local name)` pairs
+    pub fn names(&self) -> impl Iterator<Item = (Option<&str>, &str)> {
+        self.0
+             .0
+            .keys()
+            .map(|name| (name.namespace.as_deref(), &*name.local_name))
+    }
+
     /// Read a specific property from this `prop` element.
     ///
     /// Returns
@@ -29,9 +115,30 @@ impl Properties {
     {
         self.0.get_optional()
     }
+
+    /// Merge the properties of another `prop` element into this one.
+    pub fn merge(&mut self, Properties(other): Properties) {
+        for (name, value) in other.0 {
+            self.0.insert_raw(name, value);
+        }
+    }
 }
 
 impl Properties {
+    /// Read the `acl` property.
+    ///
+    /// See [`Properties::get()`] for an overview of the possible return values.
+    pub fn acl(&self) -> Option<Option<Result<Acl, Error>>> {
+        self.get()
+    }
+
+    /// Read the `resourcetype` property.
+    ///
+    /// See [`Properties::get()`] for an overview of the possible return values.
+    pub fn resourcetype(&self) -> Option<Option<Result<ResourceType, Error>>> {
+        self.get()
+    }
+
     /// Read the `creationdate` property.
     ///
     /// See [`Properties::get()`] for an overview of the possible return values.
@@ -52,6 +159,50 @@ impl Properties {
     pub fn getlastmodified(&self) -> Option<Option<Result<LastModified, Error>>> {
         self.get()
     }
+
+    /// Read the Apache mod_dav `executable` property.
+    ///
+    /// See [`Properties::get()`] for an overview of the possible return values.
+    pub fn executable(&self) -> Option<Option<Result<Executable, Error>>> {
+        self.get()
+    }
+
+    /// Read the `owner` property.
+    ///
+    /// See [`Properties::get()`] for an overview of the possible return values.
+    pub fn owner(&self) -> Option<Option<Result<Owner, Error>>> {
+        self.get()
+    }
+
+    /// Read the `group` property.
+    ///
+    /// See [`Properties::get()`] for an overview of the possible return values.
+    pub fn group(&self) -> Option<Option<Result<Group, Error>>> {
+        self.get()
+    }
+
+    /// Read the `lockdiscovery` property.
+    ///
+    /// See [`Properties::get()`] for an overview of the possible return values.
+    pub fn lockdiscovery(&self) -> Option<Option<Result<LockDiscovery, Error>>> {
+        self.get()
+    }
+
+    /// Read the `current-user-privilege-set` property.
+    ///
+    /// See [`Properties::get()`] for an overview of the possible return values.
+    pub fn current_user_privilege_set(
+        &self,
+    ) -> Option<Option<Result<CurrentUserPrivilegeSet, Error>>> {
+        self.get()
+    }
+
+    /// Read the `supportedlock` property.
+    ///
+    /// See [`Properties::get()`] for an overview of the possible return values.
+    pub fn supportedlock(&self) -> Option<Option<Result<SupportedLock, Error>>> {
+        self.get()
+    }
 }
 
 impl Element for Properties {
@@ -0,0 +1,111 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::super::elements::Properties;
+use super::super::value::ValueMap;
+use super::super::{Element, Error, OptionExt, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `propertyupdate` XML element as defined in
+/// [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_propertyupdate).
+///
+/// Since the order of the instructions is not preserved, properties are always set before being removed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PropertyUpdate {
+    pub set: Option<Set>,
+    pub remove: Option<Remove>,
+}
+
+impl Element for PropertyUpdate {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "propertyupdate";
+}
+
+impl TryFrom<&Value> for PropertyUpdate {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let map = value.to_map()?;
+        let update = Self {
+            set: map.get().transpose()?,
+            remove: map.get().transpose()?,
+        };
+        if update.set.is_none() && update.remove.is_none() {
+            return Err(Error::MissingElement(Set::LOCAL_NAME));
+        }
+
+        Ok(update)
+    }
+}
+
+impl From<PropertyUpdate> for Value {
+    fn from(PropertyUpdate { set, remove }: PropertyUpdate) -> Value {
+        let mut map = ValueMap::new();
+
+        if let Some(set) = set {
+            map.insert::<Set>(set.into());
+        }
+        if let Some(remove) = remove {
+            map.insert::<Remove>(remove.into());
+        }
+
+        Value::Map(map)
+    }
+}
+
+/// The `set` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_set).
+///
+/// Contains the properties to set along with their values.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Set(pub Properties);
+
+impl Element for Set {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "set";
+}
+
+impl TryFrom<&Value> for Set {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_map()?.get().required::<Properties>()?.map(Self)
+    }
+}
+
+impl From<Set> for Value {
+    fn from(Set(prop): Set) -> Value {
+        let mut map = ValueMap::new();
+        map.insert::<Properties>(prop.into());
+        Value::Map(map)
+    }
+}
+
+/// The `remove` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_remove).
+///
+/// Contains the names of the properties to remove.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Remove(pub Properties);
+
+impl Element for Remove {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "remove";
+}
+
+impl TryFrom<&Value> for Remove {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_map()?.get().required::<Properties>()?.map(Self)
+    }
+}
+
+impl From<Remove> for Value {
+    fn from(Remove(prop): Remove) -> Value {
+        let mut map = ValueMap::new();
+        map.insert::<Properties>(prop.into());
+        Value::Map(map)
+    }
+}
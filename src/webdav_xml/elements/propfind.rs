@@ -2,12 +2,14 @@
 //
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use bytestring::ByteString;
-
 use super::super::elements::Properties;
+use super::super::value::ValueMap;
 use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
 
 /// The `propfind` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_propfind).
+///
+/// Requests either the names of all the properties (`Propname`), all the live properties defined by
+/// RFC 4918 and the dead properties plus the `include`d ones (`Allprop`), or only the listed ones (`Prop`).
 #[derive(Clone, Debug, PartialEq)]
 pub enum Propfind {
     Propname,
@@ -44,6 +46,25 @@ impl TryFrom<&Value> for Propfind {
     }
 }
 
+impl From<Propfind> for Value {
+    fn from(propfind: Propfind) -> Value {
+        let mut map = ValueMap::new();
+
+        match propfind {
+            Propfind::Propname => map.insert::<Propname>(Value::Empty),
+            Propfind::Allprop { include } => {
+                map.insert::<Allprop>(Value::Empty);
+                if let Some(include) = include {
+                    map.insert::<Include>(include.into());
+                }
+            }
+            Propfind::Prop(prop) => map.insert::<Properties>(prop.into()),
+        }
+
+        Value::Map(map)
+    }
+}
+
 /// The `propname` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_propname).
 #[derive(Clone, Debug, PartialEq)]
 pub struct Propname;
@@ -81,8 +102,10 @@ impl TryFrom<&Value> for Allprop {
 }
 
 /// The `include` XML element as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#ELEMENT_include).
-#[derive(Clone, Debug, PartialEq)]
-pub struct Include(Vec<ByteString>);
+///
+/// Lists the properties to return along with the ones returned by `allprop`, as in a `prop` element.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Include(pub Properties);
 
 impl Element for Include {
     const NAMESPACE: &'static str = DAV_NAMESPACE;
@@ -93,7 +116,61 @@ impl Element for Include {
 impl TryFrom<&Value> for Include {
     type Error = Error;
 
-    fn try_from(_: &Value) -> Result<Self, Self::Error> {
-        todo!()
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Empty => Ok(Self::default()),
+            value => Properties::try_from(value).map(Self),
+        }
+    }
+}
+
+impl From<Include> for Value {
+    fn from(Include(prop): Include) -> Value {
+        prop.into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::webdav_xml::properties::{LastModified, LockDiscovery};
+    use crate::webdav_xml::{FromXml, IntoXml};
+
+    #[test]
+    fn should_serialize_propfind() {
+        let include = Properties::new()
+            .with::<LockDiscovery>()
+            .with_name("http://owncloud.org/ns", "x", "fileid")
+            .with_name("http://nextcloud.org/ns", "x", "has-preview");
+        let propfind = Propfind::Allprop {
+            include: Some(Include(include)),
+        };
+        let xml = propfind.clone().into_xml().unwrap();
+        let text = String::from_utf8_lossy(&xml);
+        assert!(text.contains("<d:allprop/>"), "{text}");
+        assert!(text.contains("<d:lockdiscovery/>"), "{text}");
+        // prefixes bound to different namespaces are made unique
+        assert!(
+            text.contains(r#"xmlns:x="http://owncloud.org/ns""#),
+            "{text}"
+        );
+        assert!(
+            text.contains(r#"xmlns:x1="http://nextcloud.org/ns""#),
+            "{text}"
+        );
+        assert!(text.contains("<x1:has-preview/>"), "{text}");
+        assert_eq!(Propfind::from_xml(xml).unwrap(), propfind);
+
+        for propfind in [
+            Propfind::Propname,
+            Propfind::Allprop { include: None },
+            Propfind::Prop(Properties::new().with::<LastModified>()),
+        ] {
+            let xml = propfind.clone().into_xml().unwrap();
+            assert_eq!(Propfind::from_xml(xml).unwrap(), propfind);
+        }
     }
 }
@@ -2,7 +2,7 @@
 //
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use super::super::elements::{Properties, ResponseDescription, Status};
+use super::super::elements::{Error as ErrorElement, Properties, ResponseDescription, Status};
 use super::super::value::ValueMap;
 use super::super::{Element, Error, OptionExt, Value, DAV_NAMESPACE, DAV_PREFIX};
 
@@ -11,7 +11,7 @@ use super::super::{Element, Error, OptionExt, Value, DAV_NAMESPACE, DAV_PREFIX};
 pub struct Propstat {
     pub prop: Properties,
     pub status: Status,
-    // pub error: Option<Error>,
+    pub error: Option<ErrorElement>,
     pub responsedescription: Option<ResponseDescription>,
 }
 
@@ -29,6 +29,7 @@ impl TryFrom<&Value> for Propstat {
         Ok(Self {
             prop: map.get().required::<Properties>()??,
             status: map.get().required::<Status>()??,
+            error: map.get().transpose()?,
             responsedescription: map.get().transpose()?,
         })
     }
@@ -39,6 +40,7 @@ impl From<Propstat> for Value {
         Propstat {
             prop,
             status,
+            error,
             responsedescription,
         }: Propstat,
     ) -> Value {
@@ -46,6 +48,9 @@ impl From<Propstat> for Value {
 
         map.insert::<Properties>(prop.into());
         map.insert::<Status>(status.into());
+        if let Some(error) = error {
+            map.insert::<ErrorElement>(error.into())
+        }
         if let Some(responsedescription) = responsedescription {
             map.insert::<ResponseDescription>(responsedescription.into())
         }
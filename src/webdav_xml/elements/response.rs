@@ -4,7 +4,7 @@
 
 use nonempty::NonEmpty;
 
-use super::super::elements::{Href, Propstat, ResponseDescription, Status};
+use super::super::elements::{Error as ErrorElement, Href, Propstat, ResponseDescription, Status};
 use super::super::utils::NonEmptyExt;
 use super::super::value::ValueMap;
 use super::super::{Element, Error, OptionExt, Value, DAV_NAMESPACE, DAV_PREFIX};
@@ -15,14 +15,14 @@ pub enum Response {
     Propstat {
         href: Href,
         propstat: NonEmpty<Propstat>,
-        // error: Option<Error>,
+        error: Option<ErrorElement>,
         responsedescription: Option<ResponseDescription>,
         // location: Option<Location>,
     },
     Status {
         href: NonEmpty<Href>,
         status: Status,
-        // error: Option<Error>,
+        error: Option<ErrorElement>,
         responsedescription: Option<ResponseDescription>,
         // location: Option<Location>,
     },
@@ -44,11 +44,13 @@ impl TryFrom<&Value> for Response {
             Some(propstat) => Ok(Self::Propstat {
                 href: map.get().required::<Href>()??,
                 propstat,
+                error: map.get().transpose()?,
                 responsedescription: map.get().transpose()?,
             }),
             None => Ok(Self::Status {
                 href: NonEmpty::try_collect(map.iter_all())?.required::<Href>()?,
                 status: map.get().required::<Status>()??,
+                error: map.get().transpose()?,
                 responsedescription: map.get().transpose()?,
             }),
         }
@@ -63,6 +65,7 @@ impl From<Response> for Value {
             Response::Propstat {
                 href,
                 propstat,
+                error,
                 responsedescription,
             } => {
                 map.insert::<Href>(href.into());
@@ -71,6 +74,9 @@ impl From<Response> for Value {
                         "iterator is created from a `NonEmpty` and is guaranteed to be nonempty",
                     ),
                 )));
+                if let Some(error) = error {
+                    map.insert::<ErrorElement>(error.into())
+                }
                 if let Some(responsedescription) = responsedescription {
                     map.insert::<ResponseDescription>(responsedescription.into())
                 }
@@ -78,6 +84,7 @@ impl From<Response> for Value {
             Response::Status {
                 href,
                 status,
+                error,
                 responsedescription,
             } => {
                 map.insert::<Href>(Value::List(Box::new(
@@ -86,6 +93,9 @@ impl From<Response> for Value {
                     ),
                 )));
                 map.insert::<Status>(status.into());
+                if let Some(error) = error {
+                    map.insert::<ErrorElement>(error.into())
+                }
                 if let Some(responsedescription) = responsedescription {
                     map.insert::<ResponseDescription>(responsedescription.into())
                 }
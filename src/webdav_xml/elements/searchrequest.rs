@@ -0,0 +1,230 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use bytestring::ByteString;
+
+use super::super::element::ElementName;
+use super::super::elements::{Depth, Href, Limit, Properties};
+use super::super::value::ValueMap;
+use super::super::{Element, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `searchrequest` XML element as defined in
+/// [RFC 5323](https://www.rfc-editor.org/rfc/rfc5323#section-2.2.2), containing a `basicsearch` query.
+///
+/// Search requests are only sent, so they can't be parsed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchRequest(pub BasicSearch);
+
+impl Element for SearchRequest {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "searchrequest";
+}
+
+impl From<SearchRequest> for Value {
+    fn from(SearchRequest(basicsearch): SearchRequest) -> Value {
+        let mut map = ValueMap::new();
+        map.insert::<BasicSearch>(basicsearch.into());
+        Value::Map(map)
+    }
+}
+
+/// The `basicsearch` XML element as defined in [RFC 5323](https://www.rfc-editor.org/rfc/rfc5323#section-5.2).
+///
+/// `select` are the properties returned for each result, `from` the collections to search in and `condition`
+/// the `where` clause; results are sorted according to `orderby`, then at most `limit` are returned.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BasicSearch {
+    pub select: Properties,
+    pub from: Vec<Scope>,
+    pub condition: Option<SearchCondition>,
+    pub orderby: Vec<Order>,
+    pub limit: Option<Limit>,
+}
+
+impl Element for BasicSearch {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "basicsearch";
+}
+
+impl From<BasicSearch> for Value {
+    fn from(
+        BasicSearch {
+            select,
+            from,
+            condition,
+            orderby,
+            limit,
+        }: BasicSearch,
+    ) -> Value {
+        let mut map = ValueMap::new();
+
+        let mut select_map = ValueMap::new();
+        select_map.insert::<Properties>(select.into());
+        map.insert::<Select>(Value::Map(select_map));
+
+        let mut from_map = ValueMap::new();
+        for scope in from {
+            from_map.insert::<Scope>(scope.into());
+        }
+        map.insert::<FromElement>(Value::Map(from_map));
+
+        if let Some(condition) = condition {
+            let (name, value) = condition.into_entry();
+            let mut where_map = ValueMap::new();
+            where_map.insert_raw(name, value);
+            map.insert::<Where>(Value::Map(where_map));
+        }
+
+        if !orderby.is_empty() {
+            let mut orderby_map = ValueMap::new();
+            for order in orderby {
+                orderby_map.insert::<Order>(order.into());
+            }
+            map.insert::<OrderBy>(Value::Map(orderby_map));
+        }
+
+        if let Some(limit) = limit {
+            map.insert::<Limit>(limit.into());
+        }
+
+        Value::Map(map)
+    }
+}
+
+/// The `scope` XML element as defined in [RFC 5323](https://www.rfc-editor.org/rfc/rfc5323#section-5.4.1).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scope {
+    pub href: Href,
+    pub depth: Depth,
+}
+
+impl Element for Scope {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "scope";
+}
+
+impl From<Scope> for Value {
+    fn from(Scope { href, depth }: Scope) -> Value {
+        let mut map = ValueMap::new();
+        map.insert::<Href>(href.into());
+        map.insert::<Depth>(depth.into());
+        Value::Map(map)
+    }
+}
+
+/// The search condition in the `where` XML element as defined in
+/// [RFC 5323](https://www.rfc-editor.org/rfc/rfc5323#section-5.5).
+///
+/// Comparisons hold the `prop` element naming the compared property and the literal it is compared with.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchCondition {
+    And(Vec<SearchCondition>),
+    Or(Vec<SearchCondition>),
+    Not(Box<SearchCondition>),
+    Lt(Properties, String),
+    Lte(Properties, String),
+    Gt(Properties, String),
+    Gte(Properties, String),
+    Eq(Properties, String),
+    Like(Properties, String),
+    IsCollection,
+    IsDefined(Properties),
+}
+
+impl SearchCondition {
+    /// Get the name of the operator element and its content
+    fn into_entry(self) -> (ElementName<ByteString>, Value) {
+        let (local_name, value) = match self {
+            Self::And(conditions) => ("and", Self::operands(conditions)),
+            Self::Or(conditions) => ("or", Self::operands(conditions)),
+            Self::Not(condition) => ("not", Self::operands([*condition])),
+            Self::Lt(prop, literal) => ("lt", Self::comparison(prop, literal)),
+            Self::Lte(prop, literal) => ("lte", Self::comparison(prop, literal)),
+            Self::Gt(prop, literal) => ("gt", Self::comparison(prop, literal)),
+            Self::Gte(prop, literal) => ("gte", Self::comparison(prop, literal)),
+            Self::Eq(prop, literal) => ("eq", Self::comparison(prop, literal)),
+            Self::Like(prop, pattern) => ("like", Self::comparison(prop, pattern)),
+            Self::IsCollection => ("is-collection", Value::Empty),
+            Self::IsDefined(prop) => {
+                let mut map = ValueMap::new();
+                map.insert::<Properties>(prop.into());
+                ("is-defined", Value::Map(map))
+            }
+        };
+
+        (
+            ElementName {
+                namespace: Some(DAV_NAMESPACE.into()),
+                prefix: Some(DAV_PREFIX.into()),
+                local_name: local_name.into(),
+            },
+            value,
+        )
+    }
+
+    fn operands(conditions: impl IntoIterator<Item = SearchCondition>) -> Value {
+        let mut map = ValueMap::new();
+        for condition in conditions {
+            let (name, value) = condition.into_entry();
+            map.insert_raw(name, value);
+        }
+        Value::Map(map)
+    }
+
+    fn comparison(prop: Properties, literal: String) -> Value {
+        let mut map = ValueMap::new();
+        map.insert::<Properties>(prop.into());
+        map.insert::<Literal>(literal.into());
+        Value::Map(map)
+    }
+}
+
+/// The `order` XML element as defined in [RFC 5323](https://www.rfc-editor.org/rfc/rfc5323#section-5.6.1).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Order {
+    pub prop: Properties,
+    pub descending: bool,
+}
+
+impl Element for Order {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "order";
+}
+
+impl From<Order> for Value {
+    fn from(Order { prop, descending }: Order) -> Value {
+        let mut map = ValueMap::new();
+        map.insert::<Properties>(prop.into());
+        if descending {
+            map.insert::<Descending>(Value::Empty);
+        } else {
+            map.insert::<Ascending>(Value::Empty);
+        }
+        Value::Map(map)
+    }
+}
+
+macro_rules! dav_element {
+    ($name:ident, $local_name:literal) => {
+        struct $name;
+
+        impl Element for $name {
+            const NAMESPACE: &'static str = DAV_NAMESPACE;
+            const PREFIX: &'static str = DAV_PREFIX;
+            const LOCAL_NAME: &'static str = $local_name;
+        }
+    };
+}
+
+dav_element!(Select, "select");
+dav_element!(FromElement, "from");
+dav_element!(Where, "where");
+dav_element!(OrderBy, "orderby");
+dav_element!(Literal, "literal");
+dav_element!(Ascending, "ascending");
+dav_element!(Descending, "descending");
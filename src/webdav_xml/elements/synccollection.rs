@@ -0,0 +1,140 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::super::elements::{Limit, Properties, SyncToken};
+use super::super::value::ValueMap;
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `sync-collection` XML element as defined in
+/// [RFC 6578](https://www.rfc-editor.org/rfc/rfc6578#section-6.1), the body of the `sync-collection` report.
+///
+/// Reports are only sent, so they can't be parsed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyncCollection {
+    pub sync_token: SyncToken,
+    pub sync_level: SyncLevel,
+    pub limit: Option<Limit>,
+    pub prop: Properties,
+}
+
+impl Element for SyncCollection {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "sync-collection";
+}
+
+impl From<SyncCollection> for Value {
+    fn from(
+        SyncCollection {
+            sync_token,
+            sync_level,
+            limit,
+            prop,
+        }: SyncCollection,
+    ) -> Value {
+        let mut map = ValueMap::new();
+
+        map.insert::<SyncToken>(sync_token.into());
+        map.insert::<SyncLevel>(sync_level.into());
+        if let Some(limit) = limit {
+            map.insert::<Limit>(limit.into());
+        }
+        map.insert::<Properties>(prop.into());
+
+        Value::Map(map)
+    }
+}
+
+/// The `sync-level` XML element as defined in [RFC 6578](https://www.rfc-editor.org/rfc/rfc6578#section-6.3).
+///
+/// [`SyncLevel::One`] reports the changes of the members of the collection only, [`SyncLevel::Infinite`] of
+/// all the resources in the tree.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SyncLevel {
+    #[default]
+    One,
+    Infinite,
+}
+
+impl SyncLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::One => "1",
+            Self::Infinite => "infinite",
+        }
+    }
+}
+
+impl Element for SyncLevel {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "sync-level";
+}
+
+impl FromStr for SyncLevel {
+    type Err = InvalidSyncLevel;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" => Ok(Self::One),
+            s if s.eq_ignore_ascii_case("infinite") => Ok(Self::Infinite),
+            s => Err(InvalidSyncLevel(s.into())),
+        }
+    }
+}
+
+impl Display for SyncLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<&Value> for SyncLevel {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_str()?.parse().map_err(Error::other)
+    }
+}
+
+impl From<SyncLevel> for Value {
+    fn from(level: SyncLevel) -> Value {
+        Value::Text(level.as_str().into())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid sync level: {0}")]
+pub struct InvalidSyncLevel(String);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::webdav_xml::properties::LastModified;
+    use crate::webdav_xml::IntoXml;
+
+    #[test]
+    fn should_serialize_sync_collection() {
+        let report = SyncCollection {
+            sync_token: SyncToken::default(),
+            sync_level: SyncLevel::Infinite,
+            limit: Some(Limit(100)),
+            prop: Properties::new().with::<LastModified>(),
+        };
+        let text = String::from_utf8(report.into_xml().unwrap().to_vec()).unwrap();
+        let text: String = text.split_whitespace().collect();
+        assert!(
+            text.contains(
+                "<d:sync-token/><d:sync-level>infinite</d:sync-level>\
+                 <d:limit><d:nresults>100</d:nresults></d:limit>\
+                 <d:prop><d:getlastmodified/></d:prop>"
+            ),
+            "{text}"
+        );
+    }
+}
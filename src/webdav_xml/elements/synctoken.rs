@@ -0,0 +1,41 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use bytestring::ByteString;
+
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `sync-token` XML element as defined in [RFC 6578](https://www.rfc-editor.org/rfc/rfc6578#section-6.2),
+/// also the property holding the current token of a collection.
+///
+/// An empty token asks for an initial synchronization.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SyncToken(pub ByteString);
+
+impl Element for SyncToken {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "sync-token";
+}
+
+impl TryFrom<&Value> for SyncToken {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Empty => Ok(Self::default()),
+            value => Ok(Self(value.to_str()?.clone())),
+        }
+    }
+}
+
+impl From<SyncToken> for Value {
+    fn from(SyncToken(token): SyncToken) -> Value {
+        if token.is_empty() {
+            Value::Empty
+        } else {
+            Value::Text(token)
+        }
+    }
+}
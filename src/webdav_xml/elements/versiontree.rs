@@ -0,0 +1,31 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::super::elements::Properties;
+use super::super::value::ValueMap;
+use super::super::{Element, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `version-tree` XML element as defined in
+/// [RFC 3253](https://www.rfc-editor.org/rfc/rfc3253#section-3.7), requesting the properties in `prop` for
+/// every version of a version-controlled resource.
+///
+/// Reports are only sent, so they can't be parsed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VersionTree {
+    pub prop: Properties,
+}
+
+impl Element for VersionTree {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "version-tree";
+}
+
+impl From<VersionTree> for Value {
+    fn from(VersionTree { prop }: VersionTree) -> Value {
+        let mut map = ValueMap::new();
+        map.insert::<Properties>(prop.into());
+        Value::Map(map)
+    }
+}
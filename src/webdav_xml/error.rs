@@ -23,6 +23,12 @@ pub enum Error {
     Xml(#[from] quick_xml::Error),
     #[error("unexpected tag")]
     UnexpectedTag,
+    /// A well-formed `response` element which could not be parsed; the following ones can still be read
+    #[error("invalid response{}: {source}", href.as_deref().map(|href| format!(" for {href}")).unwrap_or_default())]
+    InvalidResponse {
+        href: Option<String>,
+        source: Box<Error>,
+    },
     #[error(transparent)]
     Utf8(#[from] std::str::Utf8Error),
     #[error(transparent)]
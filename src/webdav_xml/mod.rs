@@ -11,10 +11,10 @@
 //! `<DAV:prop />` element, we can't rely on e. g. `serde` to (de)serialize
 //! XML elements.
 //!
-//! Instead, this crate uses the [`Element`](crate::Element) trait to define an
-//! element and [`FromXml`](crate::FromXml)/[`IntoXml`](crate::IntoXml) for
-//! (de)serialization.
+//! Instead, this module uses the [`Element`] trait to define an
+//! element and [`FromXml`]/[`IntoXml`] for (de)serialization.
 
+mod date;
 mod element;
 pub mod elements;
 mod error;
@@ -28,7 +28,8 @@ use bytes::{BufMut, Bytes};
 
 pub use self::element::Element;
 pub use self::error::{Error, Result};
-pub use self::value::Value;
+pub use self::read::ResponseReader;
+pub use self::value::{Value, ValueMap};
 
 /// The default WebDAV namespace
 pub const DAV_NAMESPACE: &str = "DAV:";
@@ -68,7 +69,6 @@ where
 /// As such, `IntoXml` shouldn't be implemented directly: [`Element`] and
 /// [`Into<Value>`] should be implemented instead, and you get the `IntoXml`
 /// implementation for free.
-#[allow(dead_code)]
 pub trait IntoXml: Sized {
     fn write_xml(self, writer: impl std::io::Write) -> Result<()>;
     fn into_xml(self) -> Result<Bytes> {
@@ -87,12 +87,6 @@ where
     }
 }
 
-/// A type that can't be instantiated.
-///
-/// Prevents not yet implemented types from being instantiated.
-#[derive(Clone, Debug, PartialEq)]
-enum Todo {}
-
 pub(crate) trait OptionExt<T> {
     fn required<E: Element>(self) -> Result<T>;
 }
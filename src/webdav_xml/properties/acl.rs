@@ -0,0 +1,46 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use nonempty::NonEmpty;
+
+use super::super::elements::Ace;
+use super::super::value::ValueMap;
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `acl` property as defined in [RFC 3744](https://www.rfc-editor.org/rfc/rfc3744#section-5.5).
+///
+/// Lists the access control entries of the resource, in evaluation order. The same element is the body of the
+/// `ACL` method.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Acl(pub Vec<Ace>);
+
+impl Element for Acl {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "acl";
+}
+
+impl TryFrom<&Value> for Acl {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Map(map) => map.iter_all().collect::<Result<_, _>>().map(Self),
+            _ => Ok(Self::default()),
+        }
+    }
+}
+
+impl From<Acl> for Value {
+    fn from(Acl(aces): Acl) -> Value {
+        match NonEmpty::collect(aces.into_iter().map(Value::from)) {
+            Some(aces) => {
+                let mut map = ValueMap::new();
+                map.insert::<Ace>(Value::List(Box::new(aces)));
+                Value::Map(map)
+            }
+            None => Value::Empty,
+        }
+    }
+}
@@ -5,9 +5,11 @@
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
-use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+use super::super::{date, Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
 
 /// The `creationdate` property as defined in [RFC 4918](http://webdav.org/specs/rfc4918.html#PROPERTY_creationdate).
+///
+/// Besides RFC 3339, the date is parsed leniently; e.g. IIS returns an HTTP-date.
 #[derive(Clone, Debug, PartialEq)]
 pub struct CreationDate(pub OffsetDateTime);
 
@@ -21,9 +23,7 @@ impl TryFrom<&Value> for CreationDate {
     type Error = Error;
 
     fn try_from(value: &Value) -> Result<Self, Self::Error> {
-        OffsetDateTime::parse(value.to_str()?, &Rfc3339)
-            .map(Self)
-            .map_err(Error::other)
+        date::parse(value.to_str()?).map(Self)
     }
 }
 
@@ -0,0 +1,47 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use nonempty::NonEmpty;
+
+use super::super::elements::Privilege;
+use super::super::value::ValueMap;
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `current-user-privilege-set` property as defined in
+/// [RFC 3744](https://www.rfc-editor.org/rfc/rfc3744#section-5.4).
+///
+/// Lists the privileges granted to the current user on the resource; aggregate privileges are not always
+/// expanded by the server.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CurrentUserPrivilegeSet(pub Vec<Privilege>);
+
+impl Element for CurrentUserPrivilegeSet {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "current-user-privilege-set";
+}
+
+impl TryFrom<&Value> for CurrentUserPrivilegeSet {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Map(map) => map.iter_all().collect::<Result<_, _>>().map(Self),
+            _ => Ok(Self::default()),
+        }
+    }
+}
+
+impl From<CurrentUserPrivilegeSet> for Value {
+    fn from(CurrentUserPrivilegeSet(privileges): CurrentUserPrivilegeSet) -> Value {
+        match NonEmpty::collect(privileges.into_iter().map(Value::from)) {
+            Some(privileges) => {
+                let mut map = ValueMap::new();
+                map.insert::<Privilege>(Value::List(Box::new(privileges)));
+                Value::Map(map)
+            }
+            None => Value::Empty,
+        }
+    }
+}
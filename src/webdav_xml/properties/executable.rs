@@ -0,0 +1,41 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::super::{Element, Error, Value};
+
+/// The Apache mod_dav properties namespace
+const APACHE_NAMESPACE: &str = "http://apache.org/dav/props/";
+/// The Apache mod_dav properties namespace prefix
+const APACHE_PREFIX: &str = "ap";
+
+/// The `executable` property as returned by
+/// [Apache mod_dav](https://httpd.apache.org/docs/2.4/mod/mod_dav.html).
+///
+/// Tells whether the file is executable; the value is either `T` or `F`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Executable(pub bool);
+
+impl Element for Executable {
+    const NAMESPACE: &'static str = APACHE_NAMESPACE;
+    const PREFIX: &'static str = APACHE_PREFIX;
+    const LOCAL_NAME: &'static str = "executable";
+}
+
+impl TryFrom<&Value> for Executable {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value.to_str()?.trim() {
+            "T" | "t" => Ok(Self(true)),
+            "F" | "f" => Ok(Self(false)),
+            _ => Err(Error::InvalidValueType("expected `T` or `F`")),
+        }
+    }
+}
+
+impl From<Executable> for Value {
+    fn from(Executable(executable): Executable) -> Value {
+        Value::Text(if executable { "T" } else { "F" }.into())
+    }
+}
@@ -2,14 +2,16 @@
 //
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use httpdate::HttpDate;
+use time::OffsetDateTime;
 
-use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+use super::super::{date, Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
 
 /// The `getlastmodified` property as defined in
 /// [RFC 4918](http://webdav.org/specs/rfc4918.html#PROPERTY_getlastmodified).
+///
+/// Besides the HTTP-date formats, the date is parsed leniently; e.g. some servers return RFC 3339.
 #[derive(Clone, Debug, PartialEq)]
-pub struct LastModified(pub HttpDate);
+pub struct LastModified(pub OffsetDateTime);
 
 impl Element for LastModified {
     const NAMESPACE: &'static str = DAV_NAMESPACE;
@@ -21,12 +23,30 @@ impl TryFrom<&Value> for LastModified {
     type Error = Error;
 
     fn try_from(value: &Value) -> Result<Self, Self::Error> {
-        value.to_str()?.parse().map(Self).map_err(Error::other)
+        date::parse(value.to_str()?).map(Self)
     }
 }
 
 impl From<LastModified> for Value {
     fn from(LastModified(date): LastModified) -> Value {
-        date.to_string().into()
+        date::format_http_date(date).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_serialize_as_http_date() {
+        let date =
+            LastModified::try_from(&Value::Text("2024-03-02T16:44:46+01:00".into())).unwrap();
+        assert_eq!(
+            Value::from(date),
+            Value::Text("Sat, 02 Mar 2024 15:44:46 GMT".into())
+        );
     }
 }
@@ -0,0 +1,90 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use nonempty::NonEmpty;
+
+use super::super::elements::Href;
+use super::super::value::ValueMap;
+use super::super::{Element, Error, Value};
+
+/// The CalDAV namespace
+const CALDAV_NAMESPACE: &str = "urn:ietf:params:xml:ns:caldav";
+/// The CalDAV namespace prefix
+const CALDAV_PREFIX: &str = "cal";
+/// The CardDAV namespace
+const CARDDAV_NAMESPACE: &str = "urn:ietf:params:xml:ns:carddav";
+/// The CardDAV namespace prefix
+const CARDDAV_PREFIX: &str = "card";
+
+/// The `calendar-home-set` property as defined in
+/// [RFC 4791](https://www.rfc-editor.org/rfc/rfc4791#section-6.2.1).
+///
+/// The collections containing the calendars of a principal.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CalendarHomeSet(pub Vec<Href>);
+
+impl Element for CalendarHomeSet {
+    const NAMESPACE: &'static str = CALDAV_NAMESPACE;
+    const PREFIX: &'static str = CALDAV_PREFIX;
+    const LOCAL_NAME: &'static str = "calendar-home-set";
+}
+
+impl TryFrom<&Value> for CalendarHomeSet {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        hrefs(value).map(Self)
+    }
+}
+
+impl From<CalendarHomeSet> for Value {
+    fn from(CalendarHomeSet(hrefs): CalendarHomeSet) -> Value {
+        hrefs_value(hrefs)
+    }
+}
+
+/// The `addressbook-home-set` property as defined in
+/// [RFC 6352](https://www.rfc-editor.org/rfc/rfc6352#section-7.1.1).
+///
+/// The collections containing the address books of a principal.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressbookHomeSet(pub Vec<Href>);
+
+impl Element for AddressbookHomeSet {
+    const NAMESPACE: &'static str = CARDDAV_NAMESPACE;
+    const PREFIX: &'static str = CARDDAV_PREFIX;
+    const LOCAL_NAME: &'static str = "addressbook-home-set";
+}
+
+impl TryFrom<&Value> for AddressbookHomeSet {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        hrefs(value).map(Self)
+    }
+}
+
+impl From<AddressbookHomeSet> for Value {
+    fn from(AddressbookHomeSet(hrefs): AddressbookHomeSet) -> Value {
+        hrefs_value(hrefs)
+    }
+}
+
+fn hrefs(value: &Value) -> Result<Vec<Href>, Error> {
+    match value {
+        Value::Map(map) => map.iter_all().collect(),
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn hrefs_value(hrefs: Vec<Href>) -> Value {
+    match NonEmpty::collect(hrefs.into_iter().map(Value::from)) {
+        Some(hrefs) => {
+            let mut map = ValueMap::new();
+            map.insert::<Href>(Value::List(Box::new(hrefs)));
+            Value::Map(map)
+        }
+        None => Value::Empty,
+    }
+}
@@ -2,14 +2,18 @@
 //
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use super::super::{Element, Error, Todo, Value, DAV_NAMESPACE, DAV_PREFIX};
+use nonempty::NonEmpty;
+
+use super::super::elements::ActiveLock;
+use super::super::value::ValueMap;
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
 
-/// Not yet implemented.
-///
 /// The `lockdiscovery` property as defined in
 /// [RFC 4918](http://webdav.org/specs/rfc4918.html#PROPERTY_lockdiscovery).
-#[derive(Clone, Debug, PartialEq)]
-pub struct LockDiscovery(Todo);
+///
+/// Lists the locks currently held on the resource.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LockDiscovery(pub Vec<ActiveLock>);
 
 impl Element for LockDiscovery {
     const NAMESPACE: &'static str = DAV_NAMESPACE;
@@ -20,13 +24,23 @@ impl Element for LockDiscovery {
 impl TryFrom<&Value> for LockDiscovery {
     type Error = Error;
 
-    fn try_from(_: &Value) -> Result<Self, Self::Error> {
-        todo!()
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Map(map) => map.iter_all().collect::<Result<_, _>>().map(Self),
+            _ => Ok(Self::default()),
+        }
     }
 }
 
 impl From<LockDiscovery> for Value {
-    fn from(_: LockDiscovery) -> Value {
-        todo!()
+    fn from(LockDiscovery(locks): LockDiscovery) -> Value {
+        match NonEmpty::collect(locks.into_iter().map(Value::from)) {
+            Some(locks) => {
+                let mut map = ValueMap::new();
+                map.insert::<ActiveLock>(Value::List(Box::new(locks)));
+                Value::Map(map)
+            }
+            None => Value::Empty,
+        }
     }
 }
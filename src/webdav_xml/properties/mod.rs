@@ -5,17 +5,39 @@
 //! XML property definitions based on
 //! [RFC 4918](http://webdav.org/specs/rfc4918.html#dav.properties).
 
+mod acl;
 mod creationdate;
+mod currentuserprivilegeset;
 mod displayname;
+mod executable;
 mod getcontentlanguage;
 mod getcontentlength;
 mod getcontenttype;
 mod getetag;
 mod getlastmodified;
+mod homeset;
 mod lockdiscovery;
+mod owner;
+mod principal;
+mod quota;
 mod resourcetype;
 mod supportedlock;
+mod version;
 
+pub use self::acl::Acl;
 pub use self::creationdate::CreationDate;
+pub use self::currentuserprivilegeset::CurrentUserPrivilegeSet;
+pub use self::displayname::DisplayName;
+pub use self::executable::Executable;
 pub use self::getcontentlength::ContentLength;
+pub use self::getcontenttype::ContentType;
+pub use self::getetag::ETag;
 pub use self::getlastmodified::LastModified;
+pub use self::homeset::{AddressbookHomeSet, CalendarHomeSet};
+pub use self::lockdiscovery::LockDiscovery;
+pub use self::owner::{Group, Owner};
+pub use self::principal::{CurrentUserPrincipal, PrincipalUrl};
+pub use self::quota::{QuotaAvailableBytes, QuotaBytes, QuotaUsedBytes};
+pub use self::resourcetype::{Collection, ResourceType};
+pub use self::supportedlock::SupportedLock;
+pub use self::version::{CreatorDisplayName, VersionName};
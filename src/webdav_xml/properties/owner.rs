@@ -0,0 +1,80 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use bytestring::ByteString;
+
+use super::super::elements::Href;
+use super::super::value::ValueMap;
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `owner` property as defined in
+/// [RFC 3744](http://webdav.org/specs/rfc3744.html#PROPERTY_owner).
+///
+/// Contains the principal owning the resource; servers which don't return a principal `href` may return
+/// a plain text owner instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Owner(pub ByteString);
+
+impl Element for Owner {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "owner";
+}
+
+impl TryFrom<&Value> for Owner {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        principal(value).map(Self)
+    }
+}
+
+impl From<Owner> for Value {
+    fn from(Owner(principal): Owner) -> Value {
+        principal_value(principal)
+    }
+}
+
+/// The `group` property as defined in
+/// [RFC 3744](http://webdav.org/specs/rfc3744.html#PROPERTY_group).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Group(pub ByteString);
+
+impl Element for Group {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "group";
+}
+
+impl TryFrom<&Value> for Group {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        principal(value).map(Self)
+    }
+}
+
+impl From<Group> for Value {
+    fn from(Group(principal): Group) -> Value {
+        principal_value(principal)
+    }
+}
+
+/// Read a principal, which is either an `href` element or a text
+fn principal(value: &Value) -> Result<ByteString, Error> {
+    match value {
+        Value::Text(text) => Ok(text.clone()),
+        Value::Map(map) => map
+            .get::<Href>()
+            .ok_or(Error::MissingElement(Href::LOCAL_NAME))?
+            .map(|Href(uri)| uri.to_string().into()),
+        _ => Err(Error::InvalidValueType("expected text or `href`")),
+    }
+}
+
+fn principal_value(principal: ByteString) -> Value {
+    let mut map = ValueMap::new();
+    map.insert::<Href>(Value::Text(principal));
+    Value::Map(map)
+}
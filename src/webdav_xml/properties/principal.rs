@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::super::elements::Href;
+use super::super::value::ValueMap;
+use super::super::{Element, Error, OptionExt, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `current-user-principal` property as defined in
+/// [RFC 5397](https://www.rfc-editor.org/rfc/rfc5397#section-3).
+///
+/// Tells which principal the request has been authenticated as.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CurrentUserPrincipal {
+    Href(Href),
+    Unauthenticated,
+}
+
+impl Element for CurrentUserPrincipal {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "current-user-principal";
+}
+
+impl TryFrom<&Value> for CurrentUserPrincipal {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let map = value.to_map()?;
+        match map.get::<Href>() {
+            Some(href) => href.map(Self::Href),
+            None if map.get::<Unauthenticated>().is_some() => Ok(Self::Unauthenticated),
+            None => Err(Error::MissingElement(Href::LOCAL_NAME)),
+        }
+    }
+}
+
+impl From<CurrentUserPrincipal> for Value {
+    fn from(principal: CurrentUserPrincipal) -> Value {
+        let mut map = ValueMap::new();
+        match principal {
+            CurrentUserPrincipal::Href(href) => map.insert::<Href>(href.into()),
+            CurrentUserPrincipal::Unauthenticated => map.insert::<Unauthenticated>(Value::Empty),
+        }
+        Value::Map(map)
+    }
+}
+
+/// The `unauthenticated` XML element
+struct Unauthenticated;
+
+impl Element for Unauthenticated {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "unauthenticated";
+}
+
+impl TryFrom<&Value> for Unauthenticated {
+    type Error = Error;
+
+    fn try_from(_: &Value) -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+/// The `principal-URL` property as defined in
+/// [RFC 3744](https://www.rfc-editor.org/rfc/rfc3744#section-4.2).
+///
+/// The canonical url of a principal resource.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrincipalUrl(pub Href);
+
+impl Element for PrincipalUrl {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "principal-URL";
+}
+
+impl TryFrom<&Value> for PrincipalUrl {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_map()?.get().required::<Href>()?.map(Self)
+    }
+}
+
+impl From<PrincipalUrl> for Value {
+    fn from(PrincipalUrl(href): PrincipalUrl) -> Value {
+        let mut map = ValueMap::new();
+        map.insert::<Href>(href.into());
+        Value::Map(map)
+    }
+}
@@ -0,0 +1,118 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `quota-available-bytes` property as defined in
+/// [RFC 4331](https://www.rfc-editor.org/rfc/rfc4331#section-3).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuotaAvailableBytes(pub QuotaBytes);
+
+impl Element for QuotaAvailableBytes {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "quota-available-bytes";
+}
+
+impl TryFrom<&Value> for QuotaAvailableBytes {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_str()?.parse().map(Self).map_err(Error::other)
+    }
+}
+
+impl From<QuotaAvailableBytes> for Value {
+    fn from(QuotaAvailableBytes(bytes): QuotaAvailableBytes) -> Value {
+        bytes.to_value().into()
+    }
+}
+
+/// The `quota-used-bytes` property as defined in
+/// [RFC 4331](https://www.rfc-editor.org/rfc/rfc4331#section-4).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuotaUsedBytes(pub QuotaBytes);
+
+impl Element for QuotaUsedBytes {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "quota-used-bytes";
+}
+
+impl TryFrom<&Value> for QuotaUsedBytes {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.to_str()?.parse().map(Self).map_err(Error::other)
+    }
+}
+
+impl From<QuotaUsedBytes> for Value {
+    fn from(QuotaUsedBytes(bytes): QuotaUsedBytes) -> Value {
+        bytes.to_value().into()
+    }
+}
+
+/// Value of a quota property.
+///
+/// Besides a number of bytes, the negative values used by ownCloud and Nextcloud are understood: `-3` is an
+/// unlimited quota, while `-1` and `-2` tell the server could not compute it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuotaBytes {
+    Bytes(u64),
+    Unknown,
+    Unlimited,
+}
+
+impl QuotaBytes {
+    /// Get the number of bytes, if known and limited
+    pub fn bytes(&self) -> Option<u64> {
+        match self {
+            Self::Bytes(bytes) => Some(*bytes),
+            Self::Unknown | Self::Unlimited => None,
+        }
+    }
+
+    fn to_value(self) -> String {
+        match self {
+            Self::Bytes(bytes) => bytes.to_string(),
+            Self::Unknown => String::from("-2"),
+            Self::Unlimited => String::from("-3"),
+        }
+    }
+}
+
+impl FromStr for QuotaBytes {
+    type Err = InvalidQuota;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().parse::<i64>() {
+            Ok(bytes) if bytes >= 0 => Ok(Self::Bytes(bytes as u64)),
+            Ok(-3) => Ok(Self::Unlimited),
+            Ok(-2 | -1) => Ok(Self::Unknown),
+            _ => s
+                .trim()
+                .parse()
+                .map(Self::Bytes)
+                .map_err(|_| InvalidQuota(s.into())),
+        }
+    }
+}
+
+impl Display for QuotaBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bytes(bytes) => write!(f, "{bytes} bytes"),
+            Self::Unknown => f.write_str("unknown"),
+            Self::Unlimited => f.write_str("unlimited"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid quota: {0}")]
+pub struct InvalidQuota(String);
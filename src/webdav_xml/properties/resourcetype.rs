@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+use super::super::element::ElementExt;
 use super::super::value::ValueMap;
 use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
 
@@ -16,6 +17,15 @@ impl Element for ResourceType {
     const LOCAL_NAME: &'static str = "resourcetype";
 }
 
+impl ResourceType {
+    /// Returns whether the resource is a [`Collection`]
+    pub fn is_collection(&self) -> bool {
+        self.0
+             .0
+            .contains_key(&Collection::element_name::<&'static str>())
+    }
+}
+
 impl TryFrom<&Value> for ResourceType {
     type Error = Error;
 
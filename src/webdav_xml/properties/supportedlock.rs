@@ -2,14 +2,18 @@
 //
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use super::super::{Element, Error, Todo, Value, DAV_NAMESPACE, DAV_PREFIX};
+use nonempty::NonEmpty;
+
+use super::super::elements::LockEntry;
+use super::super::value::ValueMap;
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
 
-/// Not yet implemented.
-///
 /// The `supportedlock` property as defined in
 /// [RFC 4918](http://webdav.org/specs/rfc4918.html#PROPERTY_supportedlock).
-#[derive(Clone, Debug, PartialEq)]
-pub struct SupportedLock(Todo);
+///
+/// Lists the kinds of locks supported by the resource.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SupportedLock(pub Vec<LockEntry>);
 
 impl Element for SupportedLock {
     const NAMESPACE: &'static str = DAV_NAMESPACE;
@@ -20,13 +24,23 @@ impl Element for SupportedLock {
 impl TryFrom<&Value> for SupportedLock {
     type Error = Error;
 
-    fn try_from(_: &Value) -> Result<Self, Self::Error> {
-        todo!()
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Map(map) => map.iter_all().collect::<Result<_, _>>().map(Self),
+            _ => Ok(Self::default()),
+        }
     }
 }
 
 impl From<SupportedLock> for Value {
-    fn from(_: SupportedLock) -> Value {
-        todo!()
+    fn from(SupportedLock(entries): SupportedLock) -> Value {
+        match NonEmpty::collect(entries.into_iter().map(Value::from)) {
+            Some(entries) => {
+                let mut map = ValueMap::new();
+                map.insert::<LockEntry>(Value::List(Box::new(entries)));
+                Value::Map(map)
+            }
+            None => Value::Empty,
+        }
     }
 }
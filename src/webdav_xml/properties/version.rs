@@ -0,0 +1,61 @@
+// SPDX-FileCopyrightText: d-k-bo <d-k-bo@mailbox.org>
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use bytestring::ByteString;
+
+use super::super::{Element, Error, Value, DAV_NAMESPACE, DAV_PREFIX};
+
+/// The `version-name` property as defined in
+/// [RFC 3253](https://www.rfc-editor.org/rfc/rfc3253#section-3.2.1).
+///
+/// A name identifying the version to users, e.g. `1.3`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionName(pub ByteString);
+
+impl Element for VersionName {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "version-name";
+}
+
+impl TryFrom<&Value> for VersionName {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        Ok(Self(value.to_str()?.clone()))
+    }
+}
+
+impl From<VersionName> for Value {
+    fn from(VersionName(name): VersionName) -> Value {
+        Value::Text(name)
+    }
+}
+
+/// The `creator-displayname` property as defined in
+/// [RFC 3253](https://www.rfc-editor.org/rfc/rfc3253#section-3.1.2).
+///
+/// The name of the creator of a version, meant to be displayed to users.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreatorDisplayName(pub ByteString);
+
+impl Element for CreatorDisplayName {
+    const NAMESPACE: &'static str = DAV_NAMESPACE;
+    const PREFIX: &'static str = DAV_PREFIX;
+    const LOCAL_NAME: &'static str = "creator-displayname";
+}
+
+impl TryFrom<&Value> for CreatorDisplayName {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        Ok(Self(value.to_str()?.clone()))
+    }
+}
+
+impl From<CreatorDisplayName> for Value {
+    fn from(CreatorDisplayName(name): CreatorDisplayName) -> Value {
+        Value::Text(name)
+    }
+}
@@ -7,9 +7,10 @@ use std::borrow::Cow;
 use bytestring::ByteString;
 
 use super::element::ElementName;
+use super::elements::{Href, Multistatus, Response, SyncToken};
 use super::utils::BytesExt;
 use super::value::ValueMap;
-use super::{Error, Result, Value};
+use super::{Element, Error, Result, Value};
 
 pub(crate) fn read_xml(xml: impl Into<bytes::Bytes>) -> Result<Value> {
     let xml = xml.into();
@@ -72,6 +73,8 @@ impl<'x> XmlReader<'x> {
         }
 
         let mut map = ValueMap::new();
+        // text of a mixed content element is dropped once it has child elements
+        let mut text = None;
 
         loop {
             let (resolve_result, event) = self.read_resolved_event()?;
@@ -83,17 +86,20 @@ impl<'x> XmlReader<'x> {
                 {
                     continue
                 }
-                Event::Text(text) => {
+                Event::Text(event) => {
                     // TODO: use ByteString and only reallocate when something was escaped
-                    let value = Value::Text(match text.unescape()? {
+                    let value: ByteString = match event.unescape()? {
                         Cow::Borrowed(s) => xml
                             .maybe_slice_ref(s.as_bytes())
                             .try_into()
                             .expect("string is checked by text.unescape() to be valid"),
                         Cow::Owned(s) => s.into(),
-                    });
-                    let _ = self.read_resolved_event()?;
-                    return Ok(value);
+                    };
+                    text = Some(append_text(text, value));
+                }
+                Event::CData(data) => {
+                    let value = xml.maybe_slice_ref(&data).try_into()?;
+                    text = Some(append_text(text, value));
                 }
                 Event::Start(start) => {
                     let key = key(xml, &resolve_result, &start)?;
@@ -113,10 +119,242 @@ impl<'x> XmlReader<'x> {
                 }
                 Event::End(_) | Event::Eof => break,
                 Event::Comment(_) | Event::Decl(_) | Event::PI(_) | Event::DocType(_) => continue,
-                Event::CData(_) => todo!(),
             }
         }
 
-        Ok(Value::Map(map))
+        Ok(element_value(map, text))
+    }
+}
+
+/// Append `text` to the text read so far in an element, e.g. when it is interrupted by a comment
+fn append_text(read: Option<ByteString>, text: ByteString) -> ByteString {
+    match read {
+        Some(read) => format!("{read}{text}").into(),
+        None => text,
+    }
+}
+
+/// Value of an element with the child elements `map` and the text `text`.
+///
+/// The text of an element with mixed content, e.g. `<owner>Alice <href>mailto:alice@example.com</href></owner>`,
+/// is dropped in favour of its child elements.
+fn element_value(map: ValueMap, text: Option<ByteString>) -> Value {
+    match text {
+        Some(text) if map.0.is_empty() => Value::Text(text),
+        _ => Value::Map(map),
+    }
+}
+
+/// Incremental reader of the `response` elements of a `multistatus` element, e.g. the body of a `PROPFIND`
+/// response.
+///
+/// Each `response` is parsed as soon as it has been read, so that the whole document never needs to be
+/// kept in memory. The `sync-token` element of a `sync-collection` report is kept, while other children of
+/// the `multistatus` element are skipped.
+pub struct ResponseReader<R> {
+    reader: quick_xml::NsReader<R>,
+    buf: Vec<u8>,
+    state: ReaderState,
+    sync_token: Option<SyncToken>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReaderState {
+    Prolog,
+    Multistatus,
+    Done,
+}
+
+/// An event read from a stream, owning its data
+enum Node {
+    Start(ElementName<ByteString>, Vec<u8>),
+    Empty(ElementName<ByteString>),
+    Text(ByteString),
+    End(Vec<u8>),
+    Eof,
+    Skip,
+}
+
+impl<R: std::io::BufRead> ResponseReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: quick_xml::NsReader::from_reader(reader),
+            buf: Vec::new(),
+            state: ReaderState::Prolog,
+            sync_token: None,
+        }
+    }
+
+    /// The `sync-token` element read so far; since it usually follows the `response` elements, it is only
+    /// known once all of them have been read.
+    pub fn sync_token(&self) -> Option<&SyncToken> {
+        self.sync_token.as_ref()
+    }
+
+    /// Read the next `response` element; `None` once the end of the `multistatus` element has been reached
+    ///
+    /// A `response` element which cannot be parsed is returned as an [`Error::InvalidResponse`], after which
+    /// the reading can go on.
+    fn next_response(&mut self) -> Result<Option<Response>> {
+        loop {
+            let node = self.next_node()?;
+            match (self.state, node) {
+                (ReaderState::Done, _) => return Ok(None),
+                (_, Node::Skip) => continue,
+                (ReaderState::Prolog, Node::Start(key, _)) if Self::is::<Multistatus>(&key) => {
+                    self.state = ReaderState::Multistatus;
+                }
+                (ReaderState::Prolog, Node::Empty(key)) if Self::is::<Multistatus>(&key) => {
+                    self.state = ReaderState::Done;
+                }
+                (ReaderState::Prolog, _) => {
+                    return Err(Error::MissingElement(Multistatus::LOCAL_NAME))
+                }
+                (ReaderState::Multistatus, Node::Start(key, end)) => {
+                    let value = self.read_value(&end)?;
+                    if Self::is::<Response>(&key) {
+                        return Response::try_from(&value)
+                            .map(Some)
+                            .map_err(|err| Self::invalid_response(&value, err));
+                    }
+                    if Self::is::<SyncToken>(&key) {
+                        self.sync_token = Some(SyncToken::try_from(&value)?);
+                    }
+                }
+                (ReaderState::Multistatus, Node::Empty(key)) => {
+                    if Self::is::<Response>(&key) {
+                        return Response::try_from(&Value::Empty)
+                            .map(Some)
+                            .map_err(|err| Self::invalid_response(&Value::Empty, err));
+                    }
+                    if Self::is::<SyncToken>(&key) {
+                        self.sync_token = Some(SyncToken::default());
+                    }
+                }
+                (ReaderState::Multistatus, Node::End(_)) => self.state = ReaderState::Done,
+                (ReaderState::Multistatus, Node::Text(_)) => return Err(Error::UnexpectedTag),
+                (ReaderState::Multistatus, Node::Eof) => {
+                    return Err(
+                        quick_xml::Error::UnexpectedEof(Multistatus::LOCAL_NAME.into()).into(),
+                    )
+                }
+            }
+        }
+    }
+
+    /// Read the content of the element being read until its end tag `end`
+    fn read_value(&mut self, end: &[u8]) -> Result<Value> {
+        let mut map = ValueMap::new();
+        let mut text = None;
+
+        loop {
+            match self.next_node()? {
+                Node::Skip => continue,
+                Node::Text(value) => text = Some(append_text(text, value)),
+                Node::Start(key, name) => {
+                    let value = self.read_value(&name)?;
+                    map.insert_raw(key, value);
+                }
+                Node::Empty(key) => map.insert_raw(key, Value::Empty),
+                Node::End(name) if name == end => break,
+                Node::End(_) => return Err(Error::UnexpectedTag),
+                Node::Eof => {
+                    return Err(quick_xml::Error::UnexpectedEof(
+                        String::from_utf8_lossy(end).into_owned(),
+                    )
+                    .into())
+                }
+            }
+        }
+
+        Ok(element_value(map, text))
+    }
+
+    /// Read the next event
+    fn next_node(&mut self) -> Result<Node> {
+        use quick_xml::events::{BytesStart, Event};
+        use quick_xml::name::ResolveResult;
+
+        fn key(
+            resolve_result: &ResolveResult,
+            tag: &BytesStart<'_>,
+        ) -> Result<ElementName<ByteString>> {
+            match resolve_result {
+                ResolveResult::Bound(ns) => {
+                    if ns.as_ref().is_empty() {
+                        return Err(Error::InvalidNamespace(bytes::Bytes::copy_from_slice(
+                            ns.as_ref(),
+                        )));
+                    }
+
+                    Ok(ElementName {
+                        namespace: Some(std::str::from_utf8(ns.as_ref())?.into()),
+                        prefix: None,
+                        local_name: std::str::from_utf8(tag.local_name().as_ref())?.into(),
+                    })
+                }
+                ResolveResult::Unbound | ResolveResult::Unknown(_) => Ok(ElementName {
+                    namespace: None,
+                    prefix: None,
+                    local_name: std::str::from_utf8(tag.name().as_ref())?.into(),
+                }),
+            }
+        }
+
+        self.buf.clear();
+        let (resolve_result, event) = self.reader.read_resolved_event_into(&mut self.buf)?;
+        Ok(match event {
+            Event::Text(text) => {
+                let text = text.unescape()?;
+                if text.chars().all(char::is_whitespace) {
+                    Node::Skip
+                } else {
+                    Node::Text(text.into_owned().into())
+                }
+            }
+            Event::CData(data) => Node::Text(std::str::from_utf8(&data)?.into()),
+            Event::Start(start) => Node::Start(
+                key(&resolve_result, &start)?,
+                start.name().as_ref().to_vec(),
+            ),
+            Event::Empty(tag) => Node::Empty(key(&resolve_result, &tag)?),
+            Event::End(end) => Node::End(end.name().as_ref().to_vec()),
+            Event::Eof => Node::Eof,
+            Event::Comment(_) | Event::Decl(_) | Event::PI(_) | Event::DocType(_) => Node::Skip,
+        })
+    }
+
+    /// Wrap the `error` raised parsing the `response` element `value`, keeping its `href` if there is one
+    fn invalid_response(value: &Value, error: Error) -> Error {
+        let href = value
+            .to_map()
+            .ok()
+            .and_then(|map| map.iter_all::<Href>().next())
+            .and_then(|href| href.ok())
+            .map(|href| href.0.to_string());
+
+        Error::InvalidResponse {
+            href,
+            source: Box::new(error),
+        }
+    }
+
+    fn is<E: Element>(key: &ElementName<ByteString>) -> bool {
+        key.namespace.as_deref() == Some(E::NAMESPACE) && &*key.local_name == E::LOCAL_NAME
+    }
+}
+
+impl<R: std::io::BufRead> Iterator for ResponseReader<R> {
+    type Item = Result<Response>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_response() {
+            Ok(response) => response.map(Ok),
+            Err(err @ Error::InvalidResponse { .. }) => Some(Err(err)),
+            Err(err) => {
+                self.state = ReaderState::Done;
+                Some(Err(err))
+            }
+        }
     }
 }
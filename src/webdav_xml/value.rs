@@ -65,6 +65,12 @@ impl From<String> for Value {
     }
 }
 
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Text(s.into())
+    }
+}
+
 type InnerValueMap = IndexMap<ElementName<ByteString>, Value>;
 
 /// A mapping from tag names to [`Value`]s.
@@ -177,8 +183,18 @@ impl ValueMap {
     // }
     pub(crate) fn insert_raw(&mut self, key: ElementName<ByteString>, value: Value) {
         match self.0.get_mut(&key) {
+            // append to the existing list, so that lists are never nested
+            Some(Value::List(list)) => match value {
+                Value::List(values) => list.extend(*values),
+                value => list.push(value),
+            },
             Some(old_value) => {
-                *old_value = Value::List(Box::new(nonempty![std::mem::take(old_value), value]));
+                let mut list = nonempty![std::mem::take(old_value)];
+                match value {
+                    Value::List(values) => list.extend(*values),
+                    value => list.push(value),
+                }
+                *old_value = Value::List(Box::new(list));
             }
             None => {
                 self.0.insert(key, value);
@@ -39,11 +39,15 @@ where
     fn add_namespace(&mut self, name: &ElementName<ByteString>) {
         if let Some(namespace) = &name.namespace {
             if !self.namespaces.contains_key(namespace) {
-                self.namespaces.insert(
-                    namespace.clone(),
-                    name.prefix.as_ref().cloned().unwrap_or_else(|| "NS".into()),
-                );
-                // TODO: handle collisions
+                let prefix = name.prefix.as_ref().cloned().unwrap_or_else(|| "NS".into());
+                // make the prefix unique if it's already bound to another namespace
+                let mut unique = prefix.clone();
+                let mut n = 0;
+                while self.namespaces.values().any(|p| *p == unique) {
+                    n += 1;
+                    unique = format!("{prefix}{n}").into();
+                }
+                self.namespaces.insert(namespace.clone(), unique);
             }
         }
     }